@@ -0,0 +1,80 @@
+//! Approximations of floating point functions that are only available with the standard library
+
+/// Natural logarithm of a positive, finite number
+pub(crate) fn ln(x: f32) -> f32 {
+    // Split into x = m * 2^e, with m in [1, 2)
+    let bits = x.to_bits();
+    let exponent = ((bits >> 23) & 0xff) as i32 - 127;
+    let mantissa = f32::from_bits((bits & 0x007f_ffff) | 0x3f80_0000);
+    // ln(m) = 2 * atanh((m - 1) / (m + 1)), and the series converges quickly as t <= 1/3
+    let t = (mantissa - 1.0) / (mantissa + 1.0);
+    let t2 = t * t;
+    let series = t * (1.0 + t2 * (1.0 / 3.0 + t2 * (1.0 / 5.0 + t2 * (1.0 / 7.0 + t2 / 9.0))));
+    exponent as f32 * core::f32::consts::LN_2 + 2.0 * series
+}
+
+/// Square root of a non-negative, finite number
+pub(crate) fn sqrt(x: f32) -> f32 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    // Halving the exponent gives a close first guess, which Newton's method then refines
+    let mut guess = f32::from_bits((x.to_bits() >> 1) + 0x1fc0_0000);
+    for _ in 0..4 {
+        guess = 0.5 * (guess + x / guess);
+    }
+    guess
+}
+
+/// Cosine of a finite angle in radians
+pub(crate) fn cos(x: f32) -> f32 {
+    use core::f32::consts::{FRAC_PI_2, PI, TAU};
+    // Bring the angle into [0, pi], where the cosine is the same
+    let mut x = x % TAU;
+    if x < 0.0 {
+        x = -x;
+    }
+    if x > PI {
+        x = TAU - x;
+    }
+    // Reflect the second quadrant onto the first, where the series converges quickly
+    let (x, sign) = if x > FRAC_PI_2 {
+        (PI - x, -1.0)
+    } else {
+        (x, 1.0)
+    };
+    let x2 = x * x;
+    let series = 1.0
+        - x2 / 2.0
+            * (1.0
+                - x2 / 12.0
+                    * (1.0
+                        - x2 / 30.0 * (1.0 - x2 / 56.0 * (1.0 - x2 / 90.0 * (1.0 - x2 / 132.0)))));
+    sign * series
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algos::math::{cos, ln, sqrt};
+
+    #[test]
+    fn math_approximations() {
+        for x in [1e-6f32, 0.01, 0.5, 1.0, 2.0, 10.0, 12345.0] {
+            assert!((ln(x) - (x as f64).ln() as f32).abs() < 1e-5, "ln({})", x);
+            let expected = (x as f64).sqrt() as f32;
+            assert!((sqrt(x) - expected).abs() <= expected * 1e-6, "sqrt({})", x);
+        }
+    }
+
+    #[test]
+    fn cos_approximation() {
+        for i in -40..40 {
+            let x = i as f32 * 0.37;
+            assert!(
+                (cos(x) - (x as f64).cos() as f32).abs() < 1e-6,
+                "cos({})",
+                x
+            );
+        }
+    }
+}
@@ -1,4 +1,7 @@
 //! Algorithms that are useful, but may be limited due to lack of access to system properties
 
+pub(crate) mod math;
+pub mod pathfind;
 pub mod rand;
+pub mod raster;
 pub mod slice;
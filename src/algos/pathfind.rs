@@ -0,0 +1,164 @@
+//! Path finding over graphs, without allocating.
+//!
+//! Graphs are described by implementing the [`Graph`] trait, so the nodes can be tiles on a grid,
+//! waypoints, or anything else that can be numbered.
+
+use crate::structs::IndexedMinHeap;
+use arrayvec::ArrayVec;
+use core::ops::Add;
+
+/// Identifies a node in a graph. Node ids must be below the maximum number of nodes of a search.
+pub type NodeId = usize;
+
+/// The most neighbors a node can have, which is the size of the buffer given to [`Graph::neighbors`]
+pub const MAX_NEIGHBORS: usize = 16;
+
+/// A graph that can be searched for paths
+pub trait Graph {
+    /// The cost of travelling along edges, such as a distance or a time
+    ///
+    /// The default value must be a cost of zero.
+    type Cost: Copy + Default + PartialOrd + Add<Output = Self::Cost>;
+
+    /// Write the neighbors of a node into the buffer, and return how many there are
+    fn neighbors(&self, node: NodeId, out: &mut [NodeId; MAX_NEIGHBORS]) -> usize;
+
+    /// The cost of travelling from a node to one of its neighbors
+    fn cost(&self, a: NodeId, b: NodeId) -> Self::Cost;
+
+    /// An estimate of the cost from a node to the goal
+    ///
+    /// This must never be more than the actual cost, and for every neighbor it must not be more
+    /// than the cost to the neighbor plus the estimate from the neighbor, otherwise the path found
+    /// may not be the shortest.
+    fn heuristic(&self, node: NodeId, goal: NodeId) -> Self::Cost;
+}
+
+/// Find the shortest path between two nodes using A*
+///
+/// Returns the nodes along the path, including the start and the goal, or None if the goal can not
+/// be reached. All the node ids of the graph must be below **MAX_NODES**.
+pub fn astar<const MAX_NODES: usize, G: Graph>(
+    g: &G,
+    start: NodeId,
+    goal: NodeId,
+) -> Option<ArrayVec<NodeId, MAX_NODES>> {
+    let mut cost_from_start: [Option<G::Cost>; MAX_NODES] = [None; MAX_NODES];
+    let mut came_from: [Option<NodeId>; MAX_NODES] = [None; MAX_NODES];
+    let mut closed = [false; MAX_NODES];
+    let mut open: IndexedMinHeap<G::Cost, MAX_NODES> = IndexedMinHeap::new();
+    let mut neighbors = [0; MAX_NEIGHBORS];
+
+    cost_from_start[start] = Some(G::Cost::default());
+    open.push(start, g.heuristic(start, goal));
+    while let Some(current) = open.pop_min() {
+        if current == goal {
+            return Some(reconstruct_path(&came_from, goal));
+        }
+        closed[current] = true;
+        let current_cost = cost_from_start[current]?;
+        let count = g.neighbors(current, &mut neighbors);
+        for &neighbor in &neighbors[..count.min(MAX_NEIGHBORS)] {
+            if closed[neighbor] {
+                continue;
+            }
+            let cost = current_cost + g.cost(current, neighbor);
+            let improved = match cost_from_start[neighbor] {
+                None => true,
+                Some(existing) => cost < existing,
+            };
+            if improved {
+                cost_from_start[neighbor] = Some(cost);
+                came_from[neighbor] = Some(current);
+                open.push(neighbor, cost + g.heuristic(neighbor, goal));
+            }
+        }
+    }
+    None
+}
+
+/// Walk back from the goal to the start, and return the path in the order it is travelled
+fn reconstruct_path<const MAX_NODES: usize>(
+    came_from: &[Option<NodeId>; MAX_NODES],
+    goal: NodeId,
+) -> ArrayVec<NodeId, MAX_NODES> {
+    let mut path = ArrayVec::new();
+    let mut current = Some(goal);
+    while let Some(node) = current {
+        path.push(node);
+        current = came_from[node];
+    }
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algos::pathfind::{astar, Graph, NodeId, MAX_NEIGHBORS};
+
+    /// A grid where each tile is a node, numbered row by row, and # is a wall
+    struct Grid {
+        rows: [&'static str; 4],
+    }
+
+    impl Grid {
+        fn is_open(&self, x: i32, y: i32) -> bool {
+            (0..4).contains(&x)
+                && (0..4).contains(&y)
+                && self.rows[y as usize].as_bytes()[x as usize] != b'#'
+        }
+    }
+
+    impl Graph for Grid {
+        type Cost = u32;
+
+        fn neighbors(&self, node: NodeId, out: &mut [NodeId; MAX_NEIGHBORS]) -> usize {
+            let (x, y) = ((node % 4) as i32, (node / 4) as i32);
+            let mut count = 0;
+            for (dx, dy) in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
+                if self.is_open(x + dx, y + dy) {
+                    out[count] = ((y + dy) * 4 + x + dx) as NodeId;
+                    count += 1;
+                }
+            }
+            count
+        }
+
+        fn cost(&self, _a: NodeId, _b: NodeId) -> u32 {
+            1
+        }
+
+        fn heuristic(&self, node: NodeId, goal: NodeId) -> u32 {
+            let dx = (node % 4).abs_diff(goal % 4);
+            let dy = (node / 4).abs_diff(goal / 4);
+            (dx + dy) as u32
+        }
+    }
+
+    #[test]
+    fn finds_straight_path() {
+        let grid = Grid {
+            rows: ["....", "....", "....", "...."],
+        };
+        let path = astar::<16, _>(&grid, 0, 3).unwrap();
+        assert_eq!(path.as_slice(), &[0, 1, 2, 3]);
+        assert_eq!(astar::<16, _>(&grid, 5, 5).unwrap().as_slice(), &[5]);
+    }
+
+    #[test]
+    fn finds_shortest_path_around_walls() {
+        let grid = Grid {
+            rows: [".#..", ".#.#", ".#..", "...#"],
+        };
+        let path = astar::<16, _>(&grid, 0, 2).unwrap();
+        assert_eq!(path.as_slice(), &[0, 4, 8, 12, 13, 14, 10, 6, 2]);
+    }
+
+    #[test]
+    fn unreachable_goal() {
+        let grid = Grid {
+            rows: [".#..", "##..", "....", "...."],
+        };
+        assert!(astar::<16, _>(&grid, 0, 15).is_none());
+    }
+}
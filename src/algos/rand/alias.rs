@@ -0,0 +1,142 @@
+//! Weighted sampling with the alias method.
+//!
+//! Picks random indices from a fixed distribution in constant time, after a setup in linear time.
+
+use crate::algos::rand::RandomNumberGenerator;
+use arrayvec::ArrayVec;
+
+/// A table for sampling indices in proportion to up to **S** weights, using Vose's alias method.
+///
+/// Each draw takes two random numbers and no loops, no matter how many weights there are, so this
+/// suits loot tables or spawn rates that are sampled often but rarely change.
+///
+/// ```
+/// use nostd_structs::algos::rand::alias::AliasTable;
+/// use nostd_structs::algos::rand::lcg::LcgRng;
+/// let table: AliasTable<3> = AliasTable::new(&[1.0, 0.0, 3.0]).unwrap();
+/// let mut rng = LcgRng::new(1);
+/// assert_ne!(table.sample(&mut rng), 1);
+/// ```
+pub struct AliasTable<const S: usize> {
+    /// The chance of keeping each index, rather than using its alias
+    probability: [f32; S],
+    alias: [usize; S],
+    len: usize,
+}
+
+impl<const S: usize> AliasTable<S> {
+    /// Build a table from a slice of weights, which do not need to add up to 1
+    ///
+    /// Returns an error if there are no weights, more than **S** weights, any weight is negative
+    /// or not finite, or all the weights are zero.
+    #[allow(clippy::result_unit_err)]
+    pub fn new(weights: &[f32]) -> Result<Self, ()> {
+        let len = weights.len();
+        if len == 0 || len > S || weights.iter().any(|w| !w.is_finite() || *w < 0.0) {
+            return Err(());
+        }
+        let total: f32 = weights.iter().sum();
+        if total <= 0.0 || !total.is_finite() {
+            return Err(());
+        }
+        let mut table = AliasTable {
+            probability: [1.0; S],
+            alias: [0; S],
+            len,
+        };
+        // Scale the weights so they average 1, then pair each one below 1 with one above 1
+        let mut scaled = [0.0; S];
+        let mut small: ArrayVec<usize, S> = ArrayVec::new();
+        let mut large: ArrayVec<usize, S> = ArrayVec::new();
+        for (index, weight) in weights.iter().enumerate() {
+            scaled[index] = weight * len as f32 / total;
+            if scaled[index] < 1.0 {
+                small.push(index);
+            } else {
+                large.push(index);
+            }
+        }
+        while let (Some(&less), Some(&more)) = (small.last(), large.last()) {
+            small.pop();
+            large.pop();
+            table.probability[less] = scaled[less];
+            table.alias[less] = more;
+            scaled[more] = scaled[more] + scaled[less] - 1.0;
+            if scaled[more] < 1.0 {
+                small.push(more);
+            } else {
+                large.push(more);
+            }
+        }
+        // Whatever is left is 1 apart from rounding errors, so it never uses its alias
+        for index in small.into_iter().chain(large) {
+            table.probability[index] = 1.0;
+            table.alias[index] = index;
+        }
+        Ok(table)
+    }
+
+    /// Pick a random index, with a chance proportional to its weight
+    pub fn sample<R: RandomNumberGenerator>(&self, rng: &mut R) -> usize {
+        let index = rng.next_below(self.len as u64) as usize;
+        if rng.next_f32() < self.probability[index] {
+            index
+        } else {
+            self.alias[index]
+        }
+    }
+
+    /// Returns the number of weights in the table
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the table has no weights, which can not happen for a table that was built
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algos::rand::alias::AliasTable;
+    use crate::algos::rand::lcg::LcgRng;
+
+    #[test]
+    fn frequencies_match_weights() {
+        let weights = [1.0, 2.0, 0.0, 4.0, 3.0];
+        let table: AliasTable<8> = AliasTable::new(&weights).unwrap();
+        assert_eq!(table.len(), 5);
+        let mut rng = LcgRng::new(99);
+        let draws = 100000;
+        let mut counts = [0u32; 5];
+        for _ in 0..draws {
+            counts[table.sample(&mut rng)] += 1;
+        }
+        assert_eq!(counts[2], 0);
+        for (count, weight) in counts.iter().zip(weights) {
+            let expected = draws as f32 * weight / 10.0;
+            let error = (*count as f32 - expected).abs();
+            assert!(error < draws as f32 * 0.01, "Counts were {:?}", counts);
+        }
+    }
+
+    #[test]
+    fn single_weight_always_sampled() {
+        let table: AliasTable<1> = AliasTable::new(&[0.5]).unwrap();
+        let mut rng = LcgRng::new(3);
+        for _ in 0..100 {
+            assert_eq!(table.sample(&mut rng), 0);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_weights() {
+        assert!(AliasTable::<4>::new(&[]).is_err());
+        assert!(AliasTable::<4>::new(&[0.0, 0.0]).is_err());
+        assert!(AliasTable::<4>::new(&[1.0, -1.0]).is_err());
+        assert!(AliasTable::<4>::new(&[1.0, f32::NAN]).is_err());
+        assert!(AliasTable::<4>::new(&[1.0, f32::INFINITY]).is_err());
+        assert!(AliasTable::<2>::new(&[1.0, 1.0, 1.0]).is_err());
+    }
+}
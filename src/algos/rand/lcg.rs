@@ -3,6 +3,7 @@
 //! This is a simple random number generator that is not cryptographically secure.
 
 use crate::algos::rand::RandomNumberGenerator;
+use crate::structs::{Fnv1a, NoStdHash};
 
 /// Linear congruential generator.
 ///
@@ -38,7 +39,27 @@ impl LcgRng {
         Self { state: seed }
     }
 
+    /// Create a new random number generator by mixing a buffer of entropy into a seed
+    ///
+    /// The bytes are folded with FNV-1a, so any source of entropy (such as a buffer of sensor
+    /// readings) can be used directly. An empty buffer produces a fixed, non-zero seed.
+    pub fn from_entropy(bytes: &[u8]) -> Self {
+        let hash = Fnv1a::hash_bytes(bytes);
+        // Only the lower 32 bits of the state affect the output, so fold the upper bits into them
+        Self::new((hash ^ (hash >> 32)) & 0xffff_ffff)
+    }
+
+    /// Mix a new seed into the current state
+    ///
+    /// The generator stays on its full period for any state, so the result can never be degenerate.
+    pub fn reseed(&mut self, seed: u64) {
+        // Only the lower 32 bits of the state affect the output, so fold the upper bits into them
+        self.state ^= (seed ^ (seed >> 32)) & 0xffff_ffff;
+        self.next();
+    }
+
     /// Generate the next random number
+    #[allow(clippy::should_implement_trait)]
     pub fn next(&mut self) -> u64 {
         let a: u64 = 1664525;
         let c = 1013904223;
@@ -52,12 +73,63 @@ impl RandomNumberGenerator for LcgRng {
     fn next(&mut self) -> u64 {
         self.next()
     }
+
+    fn reseed(&mut self, seed: u64) {
+        self.reseed(seed)
+    }
+}
+
+/// Linear congruential generator with a 64-bit modulus.
+///
+/// [`LcgRng`] keeps its state below 2^32, so its sequence repeats after 2^32 numbers. This
+/// generator uses the whole 64-bit state with the MMIX multiplier and increment from Knuth, so the
+/// sequence only repeats after 2^64 numbers, for any seed.
+///
+/// The low bits of an LCG are much less random than the high bits, so the two halves of the state
+/// are swapped before it is returned. This way, users of the lower 32 bits (such as
+/// [`RandomNumberGenerator::next_f32`]) get the high bits of the state.
+///
+/// ```
+/// use nostd_structs::algos::rand::lcg::Lcg64;
+/// let mut rng = Lcg64::new(42);
+/// assert_ne!(rng.next(), rng.next());
+/// ```
+pub struct Lcg64 {
+    state: u64,
+}
+
+impl Lcg64 {
+    /// Create a new random number generator with a seed
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Generate the next random number
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> u64 {
+        let a: u64 = 6364136223846793005;
+        let c: u64 = 1442695040888963407;
+        self.state = a.wrapping_mul(self.state).wrapping_add(c);
+        self.state.rotate_left(32)
+    }
+}
+
+impl RandomNumberGenerator for Lcg64 {
+    fn next(&mut self) -> u64 {
+        self.next()
+    }
+
+    fn reseed(&mut self, seed: u64) {
+        self.state ^= seed;
+        self.next();
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::collections::BTreeSet;
+    use std::vec::Vec;
 
     #[test]
     fn test_lcg() {
@@ -79,4 +151,99 @@ mod tests {
             used.insert(val);
         }
     }
+
+    #[test]
+    fn from_entropy_is_reproducible() {
+        let mut first = LcgRng::from_entropy(&[1, 2, 3, 4]);
+        let mut second = LcgRng::from_entropy(&[1, 2, 3, 4]);
+        for _ in 0..10 {
+            assert_eq!(first.next(), second.next());
+        }
+    }
+
+    #[test]
+    fn from_entropy_differs_by_input() {
+        let inputs: [&[u8]; 5] = [&[], &[0], &[1], &[0, 0], &[1, 2, 3, 4]];
+        let mut outputs = BTreeSet::new();
+        for input in inputs {
+            let mut rng = LcgRng::from_entropy(input);
+            assert!(outputs.insert((rng.next(), rng.next())));
+        }
+        assert_ne!(LcgRng::from_entropy(&[]).state, 0);
+        for input in inputs {
+            assert!(LcgRng::from_entropy(input).state <= u32::MAX as u64);
+        }
+    }
+
+    #[test]
+    fn reseed_changes_stream() {
+        let mut original = LcgRng::new(5);
+        let mut reseeded = LcgRng::new(5);
+        assert_eq!(original.next(), reseeded.next());
+        RandomNumberGenerator::reseed(&mut reseeded, 0xdead_beef);
+        let original: Vec<u64> = (0..5).map(|_| original.next()).collect();
+        let reseeded: Vec<u64> = (0..5).map(|_| reseeded.next()).collect();
+        assert_ne!(original, reseeded);
+    }
+
+    #[test]
+    fn reseed_with_large_seeds() {
+        let mut rng = LcgRng::new(0);
+        rng.next();
+        rng.reseed(1250480957332114858);
+        for seed in [u64::MAX, 1 << 63, 0xffff_ffff_0000_0000] {
+            rng.reseed(seed);
+            assert!(rng.state <= u32::MAX as u64);
+            rng.next();
+        }
+    }
+
+    #[test]
+    fn reseed_mixes_with_state() {
+        let mut first = LcgRng::new(1);
+        let mut second = LcgRng::new(2);
+        first.reseed(7);
+        second.reseed(7);
+        assert_ne!(first.next(), second.next());
+        // Reseeding with the current state clears it, which must not get stuck
+        let mut rng = LcgRng::new(9);
+        rng.reseed(9);
+        let mut used = BTreeSet::new();
+        for _ in 0..100 {
+            assert!(used.insert(rng.next()));
+        }
+    }
+
+    #[test]
+    fn test_lcg64() {
+        let mut rng = Lcg64::new(0);
+        assert_eq!(rng.next(), 17827359826607700862);
+        assert_eq!(rng.next(), 9564076820627254801);
+        assert_eq!(rng.next(), 3346879420063709218);
+        assert_eq!(rng.next(), 9160490642167962345);
+        assert_eq!(rng.next(), 7072571108086861018);
+    }
+
+    #[test]
+    fn lcg64_doesnt_repeat() {
+        let mut rng = Lcg64::new(0);
+        let mut used = BTreeSet::new();
+        for _ in 0..2000000 {
+            assert!(used.insert(rng.next()));
+        }
+        // The state is not truncated, so the upper half of the output is used too
+        assert!(used.iter().any(|val| *val > u32::MAX as u64));
+    }
+
+    #[test]
+    fn lcg64_next_below_is_uniform() {
+        let mut rng = Lcg64::new(7);
+        let mut counts = [0u32; 4];
+        for _ in 0..4000 {
+            counts[rng.next_below(4) as usize] += 1;
+        }
+        for count in counts {
+            assert!((900..1100).contains(&count), "Counts were {:?}", counts);
+        }
+    }
 }
@@ -0,0 +1,127 @@
+//! Maze generation.
+//!
+//! Generates reproducible mazes from a random number generator, such as for levels in a roguelike.
+
+use crate::algos::rand::RandomNumberGenerator;
+
+/// Generate a maze on a grid of **W** by **H** tiles, where true is a passage and false is a wall
+///
+/// The maze is carved with a randomized depth first search (the recursive backtracker). Instead of
+/// a stack, each tile remembers the direction it was reached from, so there is no extra memory
+/// and no limit on the size. The passages form a tree, so every passage can be reached from every
+/// other passage, along exactly one path.
+///
+/// The passages are on the odd rows and columns, starting at (1, 1), with walls around the edge.
+/// With an even size, the last row or column is also wall. The same seed gives the same maze.
+///
+/// ```
+/// use nostd_structs::algos::rand::lcg::LcgRng;
+/// use nostd_structs::algos::rand::maze::generate_maze;
+/// let maze: [[bool; 9]; 7] = generate_maze(&mut LcgRng::new(5));
+/// assert!(maze[1][1]);
+/// assert!(!maze[0][0]);
+/// ```
+pub fn generate_maze<const W: usize, const H: usize, R: RandomNumberGenerator>(
+    rng: &mut R,
+) -> [[bool; W]; H] {
+    const DIRECTIONS: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+    let mut maze = [[false; W]; H];
+    if W < 3 || H < 3 {
+        return maze;
+    }
+    // The index into DIRECTIONS that leads back to the tile each tile was carved from
+    let mut back = [[0u8; W]; H];
+    let (mut x, mut y) = (1, 1);
+    maze[y][x] = true;
+    loop {
+        let mut unvisited = [0u8; 4];
+        let mut count = 0;
+        for (direction, (dx, dy)) in DIRECTIONS.iter().enumerate() {
+            let (nx, ny) = (x.wrapping_add_signed(dx * 2), y.wrapping_add_signed(dy * 2));
+            if nx < W - 1 && ny < H - 1 && !maze[ny][nx] {
+                unvisited[count] = direction as u8;
+                count += 1;
+            }
+        }
+        if count > 0 {
+            let direction = unvisited[rng.next_below(count as u64) as usize];
+            let (dx, dy) = DIRECTIONS[direction as usize];
+            maze[y.wrapping_add_signed(dy)][x.wrapping_add_signed(dx)] = true;
+            x = x.wrapping_add_signed(dx * 2);
+            y = y.wrapping_add_signed(dy * 2);
+            maze[y][x] = true;
+            back[y][x] = (direction + 2) % 4;
+        } else if (x, y) == (1, 1) {
+            return maze;
+        } else {
+            let (dx, dy) = DIRECTIONS[back[y][x] as usize];
+            x = x.wrapping_add_signed(dx * 2);
+            y = y.wrapping_add_signed(dy * 2);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algos::rand::lcg::LcgRng;
+    use crate::algos::rand::maze::generate_maze;
+    use crate::algos::raster::flood_fill;
+    use core::cell::RefCell;
+
+    /// Returns the number of passages, and the number that can be reached from (1, 1)
+    fn count_reachable<const W: usize, const H: usize>(maze: [[bool; W]; H]) -> (usize, usize) {
+        let passages = maze.iter().flatten().filter(|open| **open).count();
+        let visited = RefCell::new([[false; W]; H]);
+        let mut reached = 0;
+        let complete = flood_fill::<64, _, _>(
+            (1, 1),
+            |x, y| {
+                (0..W as i32).contains(&x)
+                    && (0..H as i32).contains(&y)
+                    && maze[y as usize][x as usize]
+                    && !visited.borrow()[y as usize][x as usize]
+            },
+            |x, y| {
+                visited.borrow_mut()[y as usize][x as usize] = true;
+                reached += 1;
+            },
+        );
+        assert!(complete);
+        (passages, reached)
+    }
+
+    #[test]
+    fn maze_is_reproducible() {
+        let first: [[bool; 15]; 11] = generate_maze(&mut LcgRng::new(42));
+        let second: [[bool; 15]; 11] = generate_maze(&mut LcgRng::new(42));
+        let other: [[bool; 15]; 11] = generate_maze(&mut LcgRng::new(43));
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+    }
+
+    #[test]
+    fn maze_is_connected_tree() {
+        for seed in 0..10 {
+            let maze: [[bool; 21]; 15] = generate_maze(&mut LcgRng::new(seed));
+            let (passages, reached) = count_reachable(maze);
+            assert_eq!(passages, reached);
+            // 10 by 7 rooms, joined by one less passage than there are rooms
+            assert_eq!(passages, 70 + 69);
+            assert!(maze[0].iter().all(|open| !open));
+            assert!(maze.iter().all(|row| !row[0] && !row[20]));
+        }
+    }
+
+    #[test]
+    fn even_and_tiny_mazes() {
+        let maze: [[bool; 8]; 6] = generate_maze(&mut LcgRng::new(1));
+        let (passages, reached) = count_reachable(maze);
+        assert_eq!(passages, reached);
+        assert_eq!(passages, 6 + 5);
+        assert!(maze[5].iter().all(|open| !open));
+        let tiny: [[bool; 2]; 5] = generate_maze(&mut LcgRng::new(1));
+        assert_eq!(tiny, [[false; 2]; 5]);
+        let single: [[bool; 3]; 3] = generate_maze(&mut LcgRng::new(1));
+        assert_eq!(single.iter().flatten().filter(|open| **open).count(), 1);
+    }
+}
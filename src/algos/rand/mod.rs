@@ -1,8 +1,193 @@
 //! Random number generator algorithms
+pub mod alias;
 pub mod lcg;
+pub mod maze;
+pub mod reservoir;
+
+use crate::algos::math::{ln, sqrt};
 
 /// A trait for random number generators
 pub trait RandomNumberGenerator {
     /// Generate the next random number
     fn next(&mut self) -> u64;
+
+    /// Mix fresh entropy into the generator, without discarding its current state
+    ///
+    /// This is optional, and the default implementation ignores the seed.
+    fn reseed(&mut self, _seed: u64) {}
+
+    /// Generate a random number in the range [0, 1)
+    ///
+    /// This uses bits 8 to 31 of the next number, so it works for generators that only produce
+    /// 32 bits, and avoids the lowest bits, which are the least random in some generators.
+    fn next_f32(&mut self) -> f32 {
+        ((self.next() >> 8) & 0xff_ffff) as f32 / (1u32 << 24) as f32
+    }
+
+    /// Generate a random number in the range [0, bound)
+    ///
+    /// This scales the same bits as next_f32 into the range, rather than taking the remainder,
+    /// because the lowest bits of some generators repeat with a short period. Bits are drawn 24 at
+    /// a time, up to 64, until there are at least 8 more than the bound needs, so every number in
+    /// the range can be returned. Bounds up to 2^16 take a single number from the generator.
+    ///
+    /// The result is biased by at most 1 in 256 between numbers, and only for bounds above 2^56 can
+    /// it be more.
+    ///
+    /// The range is empty for a bound of 0, which panics in debug builds, and returns 0 otherwise.
+    fn next_below(&mut self, bound: u64) -> u64 {
+        debug_assert!(bound > 0, "The bound of next_below must be above 0");
+        let needed = 64 - bound.saturating_sub(1).leading_zeros() + 8;
+        let mut bits: u128 = 0;
+        let mut width = 0;
+        while width < needed && width < 64 {
+            bits = (bits << 24) | ((self.next() >> 8) & 0xff_ffff) as u128;
+            width += 24;
+        }
+        if width > 64 {
+            bits >>= width - 64;
+            width = 64;
+        }
+        ((bits * bound as u128) >> width) as u64
+    }
+
+    /// Fill a buffer with random bytes
+    ///
+    /// Each byte uses the top bits of the same range as next_below, so one number is generated for
+    /// each byte.
+    fn fill_bytes(&mut self, out: &mut [u8]) {
+        for byte in out.iter_mut() {
+            *byte = self.next_below(256) as u8;
+        }
+    }
+
+    /// Generate a normally distributed random number
+    ///
+    /// This uses the polar form of the Box-Muller transform, which avoids trigonometry. The
+    /// transform produces two numbers at a time, but a default method can not keep state, so the
+    /// second number is discarded.
+    fn next_gaussian(&mut self, mean: f32, std_dev: f32) -> f32 {
+        loop {
+            let u = self.next_f32() * 2.0 - 1.0;
+            let v = self.next_f32() * 2.0 - 1.0;
+            let s = u * u + v * v;
+            if s > 0.0 && s < 1.0 {
+                return mean + std_dev * u * sqrt(-2.0 * ln(s) / s);
+            }
+        }
+    }
+}
+
+/// Generate a 16 byte identifier, laid out like a version 4 (random) UUID
+///
+/// The version and variant bits are set, so the identifier can be formatted and stored as a UUID.
+/// The identifiers are only as unique as the generator is random, so a generator with a short
+/// period or a fixed seed will repeat them.
+pub fn next_id<R: RandomNumberGenerator>(rng: &mut R) -> [u8; 16] {
+    let mut id = [0; 16];
+    rng.fill_bytes(&mut id);
+    id[6] = (id[6] & 0x0f) | 0x40;
+    id[8] = (id[8] & 0x3f) | 0x80;
+    id
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algos::rand::lcg::LcgRng;
+    use crate::algos::rand::{next_id, RandomNumberGenerator};
+
+    #[test]
+    fn next_f32_in_range() {
+        let mut rng = LcgRng::new(3);
+        for _ in 0..10000 {
+            let value = rng.next_f32();
+            assert!((0.0..1.0).contains(&value));
+        }
+    }
+
+    #[test]
+    fn next_below_in_range() {
+        let mut rng = LcgRng::new(11);
+        let mut counts = [0u32; 6];
+        for _ in 0..6000 {
+            counts[rng.next_below(6) as usize] += 1;
+        }
+        for count in counts {
+            assert!((900..1100).contains(&count), "Counts were {:?}", counts);
+        }
+        assert_eq!(rng.next_below(1), 0);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn next_below_zero_panics() {
+        LcgRng::new(1).next_below(0);
+    }
+
+    #[test]
+    fn next_below_large_bounds() {
+        let mut rng = LcgRng::new(17);
+        // With only 24 random bits, the lowest bits of a result below 2^40 would always be zero
+        let bound = 1u64 << 40;
+        let mut low_counts = [0u32; 4];
+        for _ in 0..4000 {
+            let value = rng.next_below(bound);
+            assert!(value < bound);
+            low_counts[(value & 3) as usize] += 1;
+        }
+        for count in low_counts {
+            assert!((900..1100).contains(&count), "Counts were {:?}", low_counts);
+        }
+        // A bound of almost 2^64 takes the full 64 bits
+        let bound = u64::MAX - 5;
+        assert!((0..100).any(|_| rng.next_below(bound) & 0xffff != 0));
+        assert!((0..100).all(|_| rng.next_below(bound) < bound));
+    }
+
+    #[test]
+    fn gaussian_matches_parameters() {
+        let mut rng = LcgRng::new(2024);
+        let count = 20000;
+        let mut sum = 0.0f64;
+        let mut sum_squares = 0.0f64;
+        for _ in 0..count {
+            let value = rng.next_gaussian(5.0, 2.0) as f64;
+            sum += value;
+            sum_squares += value * value;
+        }
+        let mean = sum / count as f64;
+        let std_dev = (sum_squares / count as f64 - mean * mean).sqrt();
+        assert!((mean - 5.0).abs() < 0.05, "Mean was {}", mean);
+        assert!(
+            (std_dev - 2.0).abs() < 0.05,
+            "Standard deviation was {}",
+            std_dev
+        );
+    }
+
+    #[test]
+    fn fill_bytes_uses_every_value() {
+        let mut rng = LcgRng::new(8);
+        let mut bytes = [0u8; 4096];
+        rng.fill_bytes(&mut bytes);
+        let mut seen = [false; 256];
+        for byte in bytes {
+            seen[byte as usize] = true;
+        }
+        assert!(seen.iter().all(|seen| *seen));
+    }
+
+    #[test]
+    fn ids_have_version_and_variant() {
+        let mut rng = LcgRng::new(1234);
+        let first = next_id(&mut rng);
+        let second = next_id(&mut rng);
+        assert_ne!(first, second);
+        for id in [first, second] {
+            assert_eq!(id[6] >> 4, 4);
+            assert_eq!(id[8] >> 6, 0b10);
+        }
+        assert_eq!(next_id(&mut LcgRng::new(1234)), first);
+    }
 }
@@ -0,0 +1,128 @@
+//! Reservoir sampling.
+//!
+//! Picks a uniformly random sample of items from a stream, without knowing its length in advance.
+
+use crate::algos::rand::RandomNumberGenerator;
+use arrayvec::ArrayVec;
+
+/// Keeps a random sample of up to **K** items from a stream of items, using Algorithm R.
+///
+/// Every item offered has the same chance of being in the sample, no matter how long the stream
+/// is, and only the sample is stored.
+///
+/// ```
+/// use nostd_structs::algos::rand::lcg::LcgRng;
+/// use nostd_structs::algos::rand::reservoir::ReservoirSampler;
+/// let mut rng = LcgRng::new(1);
+/// let mut sampler: ReservoirSampler<u32, 3> = ReservoirSampler::new();
+/// for reading in 0..100 {
+///     sampler.offer(reading, &mut rng);
+/// }
+/// assert_eq!(sampler.sample().len(), 3);
+/// ```
+pub struct ReservoirSampler<T, const K: usize> {
+    reservoir: ArrayVec<T, K>,
+    seen: u64,
+}
+
+impl<T, const K: usize> ReservoirSampler<T, K> {
+    /// Create a new, empty sampler
+    pub const fn new() -> Self {
+        ReservoirSampler {
+            reservoir: ArrayVec::new_const(),
+            seen: 0,
+        }
+    }
+
+    /// Offer the next item of the stream to the sampler
+    ///
+    /// The first **K** items always go into the sample. After that, an item replaces a random
+    /// item of the sample with a chance of K in the number of items seen so far. Once the sample is
+    /// full, one random number is drawn for each item, or more once over 2^16 items have been seen,
+    /// so that the chance stays accurate for long streams.
+    pub fn offer<R: RandomNumberGenerator>(&mut self, item: T, rng: &mut R) {
+        self.seen += 1;
+        if !self.reservoir.is_full() {
+            self.reservoir.push(item);
+            return;
+        }
+        let index = rng.next_below(self.seen);
+        if index < K as u64 {
+            self.reservoir[index as usize] = item;
+        }
+    }
+
+    /// Returns the current sample
+    pub fn sample(&self) -> &[T] {
+        &self.reservoir
+    }
+
+    /// Returns how many items have been offered
+    pub fn seen(&self) -> u64 {
+        self.seen
+    }
+}
+
+impl<T, const K: usize> Default for ReservoirSampler<T, K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algos::rand::lcg::LcgRng;
+    use crate::algos::rand::reservoir::ReservoirSampler;
+
+    #[test]
+    fn fills_before_replacing() {
+        let mut rng = LcgRng::new(0);
+        let mut sampler: ReservoirSampler<u32, 4> = ReservoirSampler::new();
+        for item in 0..4 {
+            sampler.offer(item, &mut rng);
+            assert_eq!(sampler.sample().len(), item as usize + 1);
+        }
+        assert_eq!(sampler.sample(), &[0, 1, 2, 3]);
+        // Filling the reservoir does not use the generator
+        assert_eq!(rng.next(), LcgRng::new(0).next());
+    }
+
+    #[test]
+    fn sample_is_deterministic() {
+        let sample_with_seed = |seed| {
+            let mut rng = LcgRng::new(seed);
+            let mut sampler: ReservoirSampler<u32, 5> = ReservoirSampler::new();
+            for item in 0..1000 {
+                sampler.offer(item, &mut rng);
+            }
+            assert_eq!(sampler.seen(), 1000);
+            let mut sample = [0; 5];
+            sample.copy_from_slice(sampler.sample());
+            sample
+        };
+        let sample = sample_with_seed(77);
+        assert_eq!(sample, sample_with_seed(77));
+        assert_ne!(sample, sample_with_seed(78));
+        // Over a long stream the early items are very likely replaced
+        assert_ne!(sample, [0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn items_are_chosen_uniformly() {
+        let mut rng = LcgRng::new(5);
+        let mut counts = [0u32; 10];
+        for _ in 0..2000 {
+            let mut sampler: ReservoirSampler<usize, 2> = ReservoirSampler::new();
+            for item in 0..10 {
+                sampler.offer(item, &mut rng);
+            }
+            for item in sampler.sample() {
+                counts[*item] += 1;
+            }
+        }
+        // Each item is expected in the sample 2000 * 2 / 10 = 400 times
+        for count in counts {
+            assert!((300..500).contains(&count), "Counts were {:?}", counts);
+        }
+    }
+}
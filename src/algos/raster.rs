@@ -0,0 +1,226 @@
+//! Rasterization algorithms for drawing primitives onto a pixel grid
+//!
+//! The functions in this module do not own a framebuffer. Instead, they invoke a callback for
+//! every pixel that should be drawn, so they can be used with any display or buffer.
+
+use arrayvec::ArrayVec;
+
+/// Plot the outline of a circle using the midpoint circle algorithm
+///
+/// Only one octant is computed, and the other seven are derived from symmetry. Each pixel is
+/// plotted exactly once, so the callback can be used with blending or XOR drawing.
+///
+/// A radius of 0 plots the center point only. A negative radius plots nothing.
+///
+/// ```
+/// use nostd_structs::algos::raster::plot_circle;
+/// let mut count = 0;
+/// plot_circle(10, 10, 1, |_x, _y| count += 1);
+/// assert_eq!(count, 4);
+/// ```
+pub fn plot_circle<F: FnMut(i32, i32)>(cx: i32, cy: i32, radius: i32, mut plot: F) {
+    if radius < 0 {
+        return;
+    }
+    if radius == 0 {
+        plot(cx, cy);
+        return;
+    }
+    let mut x = radius;
+    let mut y = 0;
+    let mut err = 1 - radius;
+    while x >= y {
+        if y == 0 {
+            // The points on the axes are shared by two octants each
+            plot(cx + x, cy);
+            plot(cx - x, cy);
+            plot(cx, cy + x);
+            plot(cx, cy - x);
+        } else if x == y {
+            // The points on the diagonals are shared by two octants each
+            plot(cx + x, cy + y);
+            plot(cx - x, cy + y);
+            plot(cx + x, cy - y);
+            plot(cx - x, cy - y);
+        } else {
+            plot(cx + x, cy + y);
+            plot(cx - x, cy + y);
+            plot(cx + x, cy - y);
+            plot(cx - x, cy - y);
+            plot(cx + y, cy + x);
+            plot(cx - y, cy + x);
+            plot(cx + y, cy - x);
+            plot(cx - y, cy - x);
+        }
+        y += 1;
+        if err < 0 {
+            err += 2 * y + 1;
+        } else {
+            x -= 1;
+            err += 2 * (y - x) + 1;
+        }
+    }
+}
+
+/// Fill a 4-connected region of a grid, starting from a point, without recursion
+///
+/// should_fill returns true for the points that are part of the region and have not been filled
+/// yet, so it must return false for a point once fill has been called for it. Both are usually
+/// backed by the same buffer, for example through a `RefCell`.
+///
+/// The region is filled one horizontal run at a time, and only the start of each run above and
+/// below is remembered on a stack of size **STACK**. Returns false if the stack ran out, in which
+/// case parts of the region may not have been filled.
+pub fn flood_fill<const STACK: usize, F, G>(
+    start: (i32, i32),
+    mut should_fill: F,
+    mut fill: G,
+) -> bool
+where
+    F: FnMut(i32, i32) -> bool,
+    G: FnMut(i32, i32),
+{
+    let mut stack: ArrayVec<(i32, i32), STACK> = ArrayVec::new();
+    let mut complete = stack.try_push(start).is_ok();
+    while let Some((x, y)) = stack.pop() {
+        if !should_fill(x, y) {
+            continue;
+        }
+        let mut x = x;
+        while should_fill(x - 1, y) {
+            x -= 1;
+        }
+        // Whether the previous point above or below was part of a run that is already on the stack
+        let mut run_above = false;
+        let mut run_below = false;
+        while should_fill(x, y) {
+            fill(x, y);
+            for (next_y, in_run) in [(y - 1, &mut run_above), (y + 1, &mut run_below)] {
+                if !should_fill(x, next_y) {
+                    *in_run = false;
+                } else if !*in_run {
+                    *in_run = true;
+                    complete &= stack.try_push((x, next_y)).is_ok();
+                }
+            }
+            x += 1;
+        }
+    }
+    complete
+}
+
+#[cfg(test)]
+mod test {
+    use crate::algos::raster::{flood_fill, plot_circle};
+    use std::cell::RefCell;
+    use std::collections::BTreeSet;
+    use std::vec::Vec;
+
+    fn collect_circle(cx: i32, cy: i32, radius: i32) -> Vec<(i32, i32)> {
+        let mut points = Vec::new();
+        plot_circle(cx, cy, radius, |x, y| points.push((x, y)));
+        points
+    }
+
+    #[test]
+    fn radius_zero_and_one() {
+        assert_eq!(collect_circle(3, 4, 0), [(3, 4)]);
+        let points: BTreeSet<_> = collect_circle(0, 0, 1).into_iter().collect();
+        assert_eq!(points, BTreeSet::from([(1, 0), (-1, 0), (0, 1), (0, -1)]));
+        assert!(collect_circle(0, 0, -1).is_empty());
+    }
+
+    #[test]
+    fn circle_is_symmetric_and_unique() {
+        let points = collect_circle(0, 0, 3);
+        let unique: BTreeSet<_> = points.iter().copied().collect();
+        assert_eq!(points.len(), unique.len(), "A pixel was plotted twice");
+        assert_eq!(points.len(), 16);
+        // The first octant, from the x-axis up to the diagonal
+        for octant_point in [(3, 0), (3, 1), (2, 2)] {
+            assert!(unique.contains(&octant_point));
+        }
+        for &(x, y) in unique.iter() {
+            assert!(unique.contains(&(-x, y)));
+            assert!(unique.contains(&(x, -y)));
+            assert!(unique.contains(&(y, x)));
+        }
+    }
+
+    #[test]
+    fn circle_is_offset_by_center() {
+        let origin: BTreeSet<_> = collect_circle(0, 0, 5).into_iter().collect();
+        let offset: BTreeSet<_> = collect_circle(7, -2, 5)
+            .into_iter()
+            .map(|(x, y)| (x - 7, y + 2))
+            .collect();
+        assert_eq!(origin, offset);
+    }
+
+    /// Parse a grid where # is a barrier, and fill it from a start point
+    fn fill_grid<const STACK: usize>(rows: [&str; 6], start: (i32, i32)) -> (bool, [[u8; 8]; 6]) {
+        let mut grid = [[0u8; 8]; 6];
+        for (y, row) in rows.iter().enumerate() {
+            for (x, c) in row.bytes().enumerate() {
+                grid[y][x] = c;
+            }
+        }
+        let grid = RefCell::new(grid);
+        let complete = flood_fill::<STACK, _, _>(
+            start,
+            |x, y| {
+                (0..8).contains(&x)
+                    && (0..6).contains(&y)
+                    && grid.borrow()[y as usize][x as usize] == b'.'
+            },
+            |x, y| grid.borrow_mut()[y as usize][x as usize] = b'o',
+        );
+        (complete, grid.into_inner())
+    }
+
+    fn count(grid: &[[u8; 8]; 6], tile: u8) -> usize {
+        grid.iter().flatten().filter(|t| **t == tile).count()
+    }
+
+    #[test]
+    fn fill_stops_at_barriers() {
+        #[rustfmt::skip]
+        let rows = [
+            "........",
+            ".####...",
+            ".#..#...",
+            ".#...#..",
+            ".#####..",
+            "........",
+        ];
+        let (complete, grid) = fill_grid::<8>(rows, (2, 2));
+        assert!(complete);
+        assert_eq!(count(&grid, b'o'), 5);
+        assert_eq!(&grid[2][..5], b".#oo#");
+        assert_eq!(&grid[3][..6], b".#ooo#");
+        // Filling from outside reaches everything except the inside of the box
+        let (complete, grid) = fill_grid::<8>(rows, (0, 0));
+        assert!(complete);
+        assert_eq!(count(&grid, b'.'), 5);
+    }
+
+    #[test]
+    fn fill_respects_stack_bound() {
+        // Each column of the comb is a run that has to be remembered
+        #[rustfmt::skip]
+        let rows = [
+            "........",
+            ".#.#.#.#",
+            ".#.#.#.#",
+            ".#.#.#.#",
+            "########",
+            "########",
+        ];
+        let (complete, grid) = fill_grid::<4>(rows, (0, 0));
+        assert!(complete);
+        assert_eq!(count(&grid, b'.'), 0);
+        let (complete, grid) = fill_grid::<1>(rows, (0, 3));
+        assert!(!complete);
+        assert!(count(&grid, b'.') > 0);
+    }
+}
@@ -0,0 +1,65 @@
+use crate::algos::slice::find_fitting_subslice;
+use arrayvec::{ArrayString, ArrayVec};
+use core::cmp::min;
+
+/// Render the visible lines of a scrolling menu, keeping the selection in the middle
+///
+/// The options are windowed with [`find_fitting_subslice`], and each line is truncated or padded
+/// with spaces to fill **W** bytes. The selected line starts with `> ` and the others with two
+/// spaces, so the marker takes up 2 bytes of the width. Truncation never splits a character.
+///
+/// The height is limited to the capacity **H** of the returned lines.
+///
+/// ```
+/// use nostd_structs::algos::slice::render_menu;
+/// let lines = render_menu::<8, 3>(&["Start", "Options", "Quit"], 1, 3);
+/// assert_eq!(lines[0].as_str(), "  Start ");
+/// assert_eq!(lines[1].as_str(), "> Option");
+/// ```
+pub fn render_menu<const W: usize, const H: usize>(
+    options: &[&str],
+    selection: usize,
+    height: usize,
+) -> ArrayVec<ArrayString<W>, H> {
+    let (visible, start) = find_fitting_subslice(options, selection, min(height, H));
+    let mut lines = ArrayVec::new();
+    for (i, option) in visible.iter().enumerate() {
+        let mut line = ArrayString::new();
+        let marker = if start + i == selection { "> " } else { "  " };
+        for c in marker.chars().chain(option.chars()) {
+            if line.try_push(c).is_err() {
+                break;
+            }
+        }
+        while line.try_push(' ').is_ok() {}
+        lines.push(line);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod test {
+    use crate::algos::slice::menu::render_menu;
+
+    const OPTIONS: [&str; 7] = ["New", "Load", "Save", "Settings", "Credits", "Help", "Quit"];
+
+    #[test]
+    fn selection_is_centered() {
+        let lines = render_menu::<10, 3>(&OPTIONS, 3, 3);
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].as_str(), "  Save    ");
+        assert_eq!(lines[1].as_str(), "> Settings");
+        assert_eq!(lines[2].as_str(), "  Credits ");
+    }
+
+    #[test]
+    fn lines_are_padded_and_truncated() {
+        let lines = render_menu::<6, 8>(&OPTIONS, 0, 20);
+        assert_eq!(lines.len(), 7);
+        assert!(lines.iter().all(|line| line.len() == 6));
+        assert_eq!(lines[0].as_str(), "> New ");
+        assert_eq!(lines[3].as_str(), "  Sett");
+        let lines = render_menu::<5, 1>(&["é€"], 0, 1);
+        assert_eq!(lines[0].as_str(), "> é ");
+    }
+}
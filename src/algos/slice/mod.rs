@@ -1,7 +1,13 @@
 //! Algorithms that are useful for handling slices of data (such as characters of text, or bytes)
 
+mod menu;
 mod rotating;
+mod sort;
+mod split;
 mod subslice;
 
-pub use rotating::rotate_slice;
-pub use subslice::find_fitting_subslice;
+pub use menu::render_menu;
+pub use rotating::{rotate_slice, rotate_slice_jittered, rotate_str};
+pub use sort::{heapsort_by, select_nth_by, total_cmp_f32, total_cmp_f64};
+pub use split::split_fields;
+pub use subslice::{find_fitting_subslice, find_fitting_subslice_wrapping};
@@ -1,3 +1,4 @@
+use crate::algos::rand::RandomNumberGenerator;
 use core::cmp::min;
 
 /// Given a slice of items, such as characters, return 2 slices that can be used to rotate the
@@ -23,36 +24,89 @@ pub fn rotate_slice<T>(source: &[T], frame: usize, step: usize, width: usize) ->
     }
 }
 
+/// Same as [`rotate_slice`], but for text, rotating by characters instead of bytes.
+///
+/// Slicing the bytes of a UTF-8 string can cut a multi-byte character in half. This function
+/// always splits on character boundaries, so both returned slices are valid strings and `width`
+/// is measured in characters.
+pub fn rotate_str(source: &str, frame: usize, step: usize, width: usize) -> (&str, &str) {
+    let char_count = source.chars().count();
+    if char_count == 0 {
+        return ("", "");
+    }
+    // Convert a character index into the byte index where that character starts
+    let byte_index = |char_index: usize| {
+        source
+            .char_indices()
+            .nth(char_index)
+            .map_or(source.len(), |(byte_index, _c)| byte_index)
+    };
+    let animation_index = (frame / step) % char_count;
+    let start = byte_index(animation_index);
+    if animation_index + width < char_count {
+        (&source[start..byte_index(animation_index + width)], "")
+    } else {
+        let remaining_width = min(char_count, width - (char_count - animation_index));
+        (&source[start..], &source[..byte_index(remaining_width)])
+    }
+}
+
+/// Same as [`rotate_slice`], but the rotation occasionally pauses for an extra frame so that
+/// scrolling text looks less mechanical.
+///
+/// Whenever the animation would advance to the next position, a number is drawn from the random
+/// number generator and on average one in every `pause_one_in` advances is delayed by a frame.
+/// A value of 0 disables the pauses. When pauses are enabled, a number is drawn on every call with
+/// [`RandomNumberGenerator::next_below`], so the output is fully deterministic for a given
+/// generator state and the rotation never moves backwards.
+pub fn rotate_slice_jittered<'a, T, R: RandomNumberGenerator>(
+    source: &'a [T],
+    frame: usize,
+    step: usize,
+    width: usize,
+    pause_one_in: u64,
+    rng: &mut R,
+) -> (&'a [T], &'a [T]) {
+    let roll = pause_one_in != 0 && rng.next_below(pause_one_in) == 0;
+    // Only the frame on which the animation advances is held back, so it shows the same
+    // position as the frame before it
+    let paused = roll && frame > 0 && frame.is_multiple_of(step);
+    let frame = if paused { frame - 1 } else { frame };
+    rotate_slice(source, frame, step, width)
+}
+
 #[cfg(test)]
 mod test {
-    use crate::algos::slice::rotating::rotate_slice;
+    use crate::algos::rand::lcg::LcgRng;
+    use crate::algos::slice::rotating::{rotate_slice, rotate_slice_jittered, rotate_str};
     use std::string::String;
+    use std::vec::Vec;
 
     #[test]
     fn test_rotate_slice() {
         let input = "text rotate".as_bytes();
-        let (first, second) = rotate_slice(&input, 0, 2, 4);
+        let (first, second) = rotate_slice(input, 0, 2, 4);
         assert_eq!(first, "text".as_bytes());
         assert_eq!(second, &[]);
-        let (first, second) = rotate_slice(&input, 1, 2, 4);
+        let (first, second) = rotate_slice(input, 1, 2, 4);
         assert_eq!(first, "text".as_bytes());
         assert_eq!(second, &[]);
-        let (first, second) = rotate_slice(&input, 2, 2, 4);
+        let (first, second) = rotate_slice(input, 2, 2, 4);
         assert_eq!(first, "ext ".as_bytes());
         assert_eq!(second, &[]);
-        let (first, second) = rotate_slice(&input, 14, 2, 4);
+        let (first, second) = rotate_slice(input, 14, 2, 4);
         assert_eq!(first, "tate".as_bytes());
         assert_eq!(second, &[]);
-        let (first, second) = rotate_slice(&input, 16, 2, 4);
+        let (first, second) = rotate_slice(input, 16, 2, 4);
         assert_eq!(first, "ate".as_bytes());
         assert_eq!(second, "t".as_bytes());
-        let (first, second) = rotate_slice(&input, 18, 2, 4);
+        let (first, second) = rotate_slice(input, 18, 2, 4);
         assert_eq!(first, "te".as_bytes());
         assert_eq!(second, "te".as_bytes());
-        let (first, second) = rotate_slice(&input, 20, 2, 4);
+        let (first, second) = rotate_slice(input, 20, 2, 4);
         assert_eq!(first, "e".as_bytes());
         assert_eq!(second, "tex".as_bytes());
-        let (first, second) = rotate_slice(&input, 22, 2, 4);
+        let (first, second) = rotate_slice(input, 22, 2, 4);
         assert_eq!(first, "text".as_bytes());
         assert_eq!(second, "".as_bytes());
     }
@@ -65,4 +119,97 @@ mod test {
         assert_eq!(String::from_utf8_lossy(first), "hars....");
         assert_eq!(String::from_utf8_lossy(second), "This is 20 chars....");
     }
+
+    #[test]
+    fn rotate_str_keeps_characters_whole() {
+        let input = "café… ok";
+        assert_eq!(rotate_str(input, 0, 1, 5), ("café…", ""));
+        assert_eq!(rotate_str(input, 3, 1, 4), ("é… o", ""));
+        assert_eq!(rotate_str(input, 5, 1, 4), (" ok", "c"));
+        assert_eq!(rotate_str(input, 7, 1, 4), ("k", "caf"));
+    }
+
+    #[test]
+    fn rotate_str_matches_rotating_chars() {
+        let input = "ñandú → über";
+        let chars: Vec<char> = input.chars().collect();
+        for frame in 0..40 {
+            let (first, second) = rotate_str(input, frame, 2, 5);
+            let (expected_first, expected_second) = rotate_slice(&chars, frame, 2, 5);
+            assert_eq!(first, expected_first.iter().collect::<String>());
+            assert_eq!(second, expected_second.iter().collect::<String>());
+        }
+        assert_eq!(rotate_str("", 3, 1, 4), ("", ""));
+    }
+
+    #[test]
+    fn jittered_is_reproducible() {
+        let input = "a long line of text that scrolls".as_bytes();
+        let mut first_rng = LcgRng::new(42);
+        let mut second_rng = LcgRng::new(42);
+        for frame in 0..200 {
+            assert_eq!(
+                rotate_slice_jittered(input, frame, 3, 8, 2, &mut first_rng),
+                rotate_slice_jittered(input, frame, 3, 8, 2, &mut second_rng)
+            );
+        }
+    }
+
+    #[test]
+    fn jittered_only_pauses() {
+        let input = "a long line of text that scrolls".as_bytes();
+        let mut rng = LcgRng::new(7);
+        let mut pauses = 0;
+        for frame in 0..input.len() * 3 {
+            let jittered = rotate_slice_jittered(input, frame, 3, 8, 2, &mut rng);
+            if jittered != rotate_slice(input, frame, 3, 8) {
+                // A pause shows the previous frame, which is the previous position
+                assert_eq!(jittered, rotate_slice(input, frame - 1, 3, 8));
+                assert_eq!(frame % 3, 0);
+                pauses += 1;
+            }
+        }
+        assert!(pauses > 0);
+    }
+
+    #[test]
+    fn jittered_pause_rate() {
+        let input = "abcdefghijklmnopqrstuvwxyz".as_bytes();
+        for pause_one_in in [2u64, 4] {
+            // Every seed must pause at about the same rate, with an even step that could line up
+            // with a pattern in the low bits of the generator
+            for seed in 0..6 {
+                let mut rng = LcgRng::new(seed);
+                let mut pauses = 0usize;
+                for frame in 1..400 {
+                    let jittered =
+                        rotate_slice_jittered(input, frame, 2, 8, pause_one_in, &mut rng);
+                    if jittered != rotate_slice(input, frame, 2, 8) {
+                        pauses += 1;
+                    }
+                }
+                // There are 199 advances
+                let expected = 199 / pause_one_in as usize;
+                assert!(
+                    pauses.abs_diff(expected) < expected / 3,
+                    "Seed {} paused {} times, not about {}",
+                    seed,
+                    pauses,
+                    expected
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn jittered_without_pauses_matches() {
+        let input = "text rotate".as_bytes();
+        let mut rng = LcgRng::new(0);
+        for frame in 0..50 {
+            assert_eq!(
+                rotate_slice_jittered(input, frame, 2, 4, 0, &mut rng),
+                rotate_slice(input, frame, 2, 4)
+            );
+        }
+    }
 }
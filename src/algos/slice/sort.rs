@@ -0,0 +1,164 @@
+use core::cmp::Ordering;
+
+/// Sort a slice in place using heapsort
+///
+/// Heapsort is O(n log n) in the worst case, does not recurse, and uses no additional memory,
+/// which makes it a good fit when stack space is limited. It is not stable, so equal elements may
+/// be reordered.
+pub fn heapsort_by<T, F: FnMut(&T, &T) -> Ordering>(slice: &mut [T], mut compare: F) {
+    // Build a max-heap, starting from the last element that has children
+    for root in (0..slice.len() / 2).rev() {
+        sift_down(slice, root, &mut compare);
+    }
+    // Repeatedly move the largest element to the end, and restore the heap in front of it
+    for end in (1..slice.len()).rev() {
+        slice.swap(0, end);
+        sift_down(&mut slice[..end], 0, &mut compare);
+    }
+}
+
+/// Move the element at root down the heap until both its children are smaller than it
+fn sift_down<T, F: FnMut(&T, &T) -> Ordering>(heap: &mut [T], mut root: usize, compare: &mut F) {
+    loop {
+        let mut child = 2 * root + 1;
+        if child >= heap.len() {
+            return;
+        }
+        if child + 1 < heap.len() && compare(&heap[child], &heap[child + 1]) == Ordering::Less {
+            child += 1;
+        }
+        if compare(&heap[root], &heap[child]) != Ordering::Less {
+            return;
+        }
+        heap.swap(root, child);
+        root = child;
+    }
+}
+
+/// Reorder a slice so that the element at index n is the one that would be there if the slice was
+/// sorted, without sorting the whole slice
+///
+/// All the elements before index n are less than or equal to it, and all the elements after are
+/// greater than or equal to it. This uses quickselect without recursion, so it is O(n) on average.
+///
+/// Panics if n is not an index of the slice.
+pub fn select_nth_by<T, F: FnMut(&T, &T) -> Ordering>(slice: &mut [T], n: usize, mut compare: F) {
+    assert!(
+        n < slice.len(),
+        "Index {n} is outside a slice of {}",
+        slice.len()
+    );
+    let mut low = 0;
+    let mut high = slice.len() - 1;
+    while low < high {
+        // Use the median of the first, middle, and last elements as the pivot, moved to high
+        let mid = low + (high - low) / 2;
+        if compare(&slice[mid], &slice[low]) == Ordering::Less {
+            slice.swap(mid, low);
+        }
+        if compare(&slice[high], &slice[low]) == Ordering::Less {
+            slice.swap(high, low);
+        }
+        if compare(&slice[mid], &slice[high]) == Ordering::Less {
+            slice.swap(mid, high);
+        }
+        // Partition everything smaller than the pivot in front of it
+        let mut store = low;
+        for i in low..high {
+            if compare(&slice[i], &slice[high]) == Ordering::Less {
+                slice.swap(i, store);
+                store += 1;
+            }
+        }
+        slice.swap(store, high);
+        match n.cmp(&store) {
+            Ordering::Equal => return,
+            Ordering::Less => high = store - 1,
+            Ordering::Greater => low = store + 1,
+        }
+    }
+}
+
+/// Compare two f32 values with a total order, for use as a sort comparator
+///
+/// Unlike `partial_cmp`, this never fails, so sorting does not panic when a value is NaN. Negative
+/// NaN sorts before everything else, and positive NaN after everything else.
+pub fn total_cmp_f32(a: &f32, b: &f32) -> Ordering {
+    a.total_cmp(b)
+}
+
+/// Compare two f64 values with a total order, for use as a sort comparator
+///
+/// Unlike `partial_cmp`, this never fails, so sorting does not panic when a value is NaN. Negative
+/// NaN sorts before everything else, and positive NaN after everything else.
+pub fn total_cmp_f64(a: &f64, b: &f64) -> Ordering {
+    a.total_cmp(b)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::algos::rand::lcg::LcgRng;
+    use crate::algos::slice::sort::{heapsort_by, select_nth_by, total_cmp_f32, total_cmp_f64};
+    use std::vec::Vec;
+
+    #[test]
+    fn heapsort_reversed() {
+        let mut data = [9, 8, 7, 6, 5, 4, 3, 2, 1, 0];
+        heapsort_by(&mut data, |a, b| a.cmp(b));
+        assert_eq!(data, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        heapsort_by(&mut data, |a, b| b.cmp(a));
+        assert_eq!(data, [9, 8, 7, 6, 5, 4, 3, 2, 1, 0]);
+    }
+
+    #[test]
+    fn heapsort_matches_reference_sort() {
+        let mut rng = LcgRng::new(1234);
+        for len in [0, 1, 2, 3, 17, 100] {
+            let mut data: Vec<u64> = (0..len).map(|_| rng.next() % 50).collect();
+            let mut expected = data.clone();
+            expected.sort();
+            heapsort_by(&mut data, |a, b| a.cmp(b));
+            assert_eq!(data, expected);
+        }
+    }
+
+    #[test]
+    fn select_nth_matches_sorted() {
+        let mut rng = LcgRng::new(99);
+        let data: Vec<u64> = (0..31).map(|_| rng.next() % 20).collect();
+        let mut sorted = data.clone();
+        sorted.sort();
+        for n in [0, 1, 7, 15, 29, 30] {
+            let mut selected = data.clone();
+            select_nth_by(&mut selected, n, |a, b| a.cmp(b));
+            assert_eq!(selected[n], sorted[n], "Wrong element at {}", n);
+            assert!(selected[..n].iter().all(|v| *v <= selected[n]));
+            assert!(selected[n + 1..].iter().all(|v| *v >= selected[n]));
+        }
+    }
+
+    #[test]
+    fn select_nth_single_and_reversed() {
+        let mut single = [5];
+        select_nth_by(&mut single, 0, |a, b| a.cmp(b));
+        assert_eq!(single, [5]);
+        let mut reversed = [9, 8, 7, 6, 5, 4, 3, 2, 1, 0];
+        select_nth_by(&mut reversed, 3, |a, b| a.cmp(b));
+        assert_eq!(reversed[3], 3);
+    }
+
+    #[test]
+    fn sorts_floats_with_nan() {
+        let mut data = [3.0, f32::NAN, -1.0, f32::INFINITY, 0.5];
+        heapsort_by(&mut data, total_cmp_f32);
+        assert_eq!(data[..4], [-1.0, 0.5, 3.0, f32::INFINITY]);
+        assert!(data[4].is_nan());
+
+        let mut data = [2.0, -f64::NAN, 1.0, f64::NAN];
+        select_nth_by(&mut data, 1, total_cmp_f64);
+        assert_eq!(data[1], 1.0);
+        heapsort_by(&mut data, total_cmp_f64);
+        assert!(data[0].is_nan() && data[3].is_nan());
+        assert_eq!(data[1..3], [1.0, 2.0]);
+    }
+}
@@ -0,0 +1,64 @@
+use arrayvec::ArrayVec;
+
+/// Split a line of text into fields on a delimiter, without allocating
+///
+/// Returns up to **N** fields. If there are more fields than that, the last field holds the rest
+/// of the line, including its delimiters. Empty fields are kept, so a trailing delimiter produces
+/// an empty last field.
+///
+/// ```
+/// use nostd_structs::algos::slice::split_fields;
+/// let fields = split_fields::<3>("12,temp,21.5,ok", ',');
+/// assert_eq!(fields.as_slice(), &["12", "temp", "21.5,ok"]);
+/// ```
+pub fn split_fields<const N: usize>(line: &str, delim: char) -> ArrayVec<&str, N> {
+    let mut fields = ArrayVec::new();
+    if N == 0 {
+        return fields;
+    }
+    let mut rest = line;
+    while fields.len() < N - 1 {
+        match rest.split_once(delim) {
+            None => break,
+            Some((field, remainder)) => {
+                fields.push(field);
+                rest = remainder;
+            }
+        }
+    }
+    fields.push(rest);
+    fields
+}
+
+#[cfg(test)]
+mod test {
+    use crate::algos::slice::split::split_fields;
+
+    #[test]
+    fn splits_fields() {
+        assert_eq!(split_fields::<4>("a,b,c", ',').as_slice(), &["a", "b", "c"]);
+        assert_eq!(split_fields::<4>("", ',').as_slice(), &[""]);
+        assert_eq!(
+            split_fields::<4>("no delimiter", ',').as_slice(),
+            &["no delimiter"]
+        );
+        assert_eq!(split_fields::<4>("π→1→2", '→').as_slice(), &["π", "1", "2"]);
+    }
+
+    #[test]
+    fn keeps_empty_fields() {
+        assert_eq!(split_fields::<4>("a,,b", ',').as_slice(), &["a", "", "b"]);
+        assert_eq!(split_fields::<4>("a,b,", ',').as_slice(), &["a", "b", ""]);
+        assert_eq!(split_fields::<4>(",", ',').as_slice(), &["", ""]);
+    }
+
+    #[test]
+    fn overflow_goes_in_last_field() {
+        assert_eq!(
+            split_fields::<2>("a,b,c,d", ',').as_slice(),
+            &["a", "b,c,d"]
+        );
+        assert_eq!(split_fields::<1>("a,b", ',').as_slice(), &["a,b"]);
+        assert!(split_fields::<0>("a,b", ',').is_empty());
+    }
+}
@@ -1,3 +1,6 @@
+use arrayvec::ArrayVec;
+use core::cmp::min;
+
 /// Given a list of items (such as options in a menu), and a selection index, find a subslice that prioritises the selection being in the middle
 pub fn find_fitting_subslice<A>(options: &[A], selection: usize, height: usize) -> (&[A], usize) {
     // Handle edge case where the entire list fits within the given height
@@ -8,11 +11,7 @@ pub fn find_fitting_subslice<A>(options: &[A], selection: usize, height: usize)
     let half_height = height / 2;
 
     // Calculate the start of the subslice, trying to keep the selection centered
-    let mut start = if selection > half_height {
-        selection - half_height
-    } else {
-        0
-    };
+    let mut start = selection.saturating_sub(half_height);
 
     // Ensure the subslice fits within the bounds of the options array
     let end = if start + height > options.len() {
@@ -29,9 +28,36 @@ pub fn find_fitting_subslice<A>(options: &[A], selection: usize, height: usize)
     (&options[start..end], start)
 }
 
+/// Same as [`find_fitting_subslice`], but for cyclic lists, where the selection always stays in
+/// the middle and the window wraps around the ends of the list.
+///
+/// Returns references to the items in the window, and the index of the first item in the window.
+/// If all the options fit within the height, they are returned in order without wrapping.
+/// The height is limited to the capacity **H** of the returned window.
+pub fn find_fitting_subslice_wrapping<A, const H: usize>(
+    options: &[A],
+    selection: usize,
+    height: usize,
+) -> (ArrayVec<&A, H>, usize) {
+    let height = min(height, H);
+    let mut window = ArrayVec::new();
+    if options.len() <= height {
+        window.extend(options.iter());
+        return (window, 0);
+    }
+
+    // Start half a window before the selection, wrapping below zero to the end of the list
+    let start = (selection % options.len() + options.len() - height / 2) % options.len();
+    for i in 0..height {
+        window.push(&options[(start + i) % options.len()]);
+    }
+    (window, start)
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::algos::slice::subslice::find_fitting_subslice;
+    use crate::algos::slice::subslice::{find_fitting_subslice, find_fitting_subslice_wrapping};
+    use arrayvec::ArrayVec;
 
     #[test]
     pub fn test_find_subslice() {
@@ -57,4 +83,29 @@ mod tests {
             (&[0, 1, 2] as &[i32], 0)
         );
     }
+
+    #[test]
+    pub fn test_find_subslice_wrapping() {
+        let data = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let (window, start) = find_fitting_subslice_wrapping::<_, 5>(&data, 0, 5);
+        assert_eq!(window, ArrayVec::from([&8, &9, &1, &2, &3]));
+        assert_eq!(start, 7);
+        let (window, start) = find_fitting_subslice_wrapping::<_, 5>(&data, 8, 5);
+        assert_eq!(window, ArrayVec::from([&7, &8, &9, &1, &2]));
+        assert_eq!(start, 6);
+        let (window, start) = find_fitting_subslice_wrapping::<_, 5>(&data, 4, 5);
+        assert_eq!(window, ArrayVec::from([&3, &4, &5, &6, &7]));
+        assert_eq!(start, 2);
+    }
+
+    #[test]
+    pub fn test_find_subslice_wrapping_fits() {
+        let (window, start) = find_fitting_subslice_wrapping::<_, 8>(&[0, 1, 2], 2, 20);
+        assert_eq!(window.as_slice(), &[&0, &1, &2]);
+        assert_eq!(start, 0);
+        // The height is limited by the capacity of the window
+        let (window, start) = find_fitting_subslice_wrapping::<_, 2>(&[0, 1, 2], 0, 20);
+        assert_eq!(window.as_slice(), &[&2, &0]);
+        assert_eq!(start, 2);
+    }
 }
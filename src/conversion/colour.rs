@@ -69,15 +69,164 @@ pub const fn vflip_1bpp_single(data: u8) -> u8 {
 }
 
 /// Flip 1-bit, 8-pixel array
-pub fn vflip_1bpp_mut(mut data: &mut [u8]) {
+pub fn vflip_1bpp_mut(data: &mut [u8]) {
     for i in data.iter_mut() {
         *i = vflip_1bpp_single(*i);
     }
 }
 
+/// Linearly interpolate between two RGB565 colours
+///
+/// t is the position between the colours, where 0 returns a, and 255 returns b. Each channel is
+/// interpolated separately, and rounded to the nearest value.
+pub const fn lerp_rgb565(a: u16, b: u16, t: u8) -> u16 {
+    let r = lerp_channel((a >> 11) & 0x1f, (b >> 11) & 0x1f, t);
+    let g = lerp_channel((a >> 5) & 0x3f, (b >> 5) & 0x3f, t);
+    let b = lerp_channel(a & 0x1f, b & 0x1f, t);
+    (r << 11) | (g << 5) | b
+}
+
+const fn lerp_channel(a: u16, b: u16, t: u8) -> u16 {
+    let t = t as u32;
+    ((a as u32 * (255 - t) + b as u32 * t + 127) / 255) as u16
+}
+
+/// Calculate the luminance (perceived brightness) of an RGB888 colour
+///
+/// This uses the Rec. 601 weights of 0.299 red, 0.587 green, and 0.114 blue, in integer arithmetic.
+pub const fn luminance_rgb888(r: u8, g: u8, b: u8) -> u8 {
+    ((299 * r as u32 + 587 * g as u32 + 114 * b as u32 + 500) / 1000) as u8
+}
+
+/// Calculate the luminance (perceived brightness) of an RGB565 colour
+///
+/// The channels are scaled up to 8 bits first, so that white is 255. See [`luminance_rgb888`].
+pub const fn luminance_rgb565(c: u16) -> u8 {
+    let r = ((c >> 11) & 0x1f) as u8;
+    let g = ((c >> 5) & 0x3f) as u8;
+    let b = (c & 0x1f) as u8;
+    // Repeat the top bits in the new low bits, so the full range maps to 0-255
+    luminance_rgb888(
+        (r << 3) | (r >> 2),
+        (g << 2) | (g >> 4),
+        (b << 3) | (b >> 2),
+    )
+}
+
+/// Convert an RGB888 colour to hue, saturation, and value
+///
+/// The hue is in degrees from 0 to 359, where red is 0, green is 120, and blue is 240. The
+/// saturation and value are from 0 to 255. Grays, which have no hue, return a hue of 0.
+pub const fn rgb888_to_hsv(r: u8, g: u8, b: u8) -> (u16, u8, u8) {
+    let (r, g, b) = (r as i32, g as i32, b as i32);
+    let max = max3(r, g, b);
+    let delta = max - min3(r, g, b);
+    if delta == 0 {
+        return (0, 0, max as u8);
+    }
+    let saturation = (delta * 255 + max / 2) / max;
+    // The offset of the hue from the sector of the largest channel, in degrees times delta
+    let (sector, offset) = if max == r {
+        (0, 60 * (g - b))
+    } else if max == g {
+        (120, 60 * (b - r))
+    } else {
+        (240, 60 * (r - g))
+    };
+    let offset = if offset < 0 {
+        (offset - delta / 2) / delta
+    } else {
+        (offset + delta / 2) / delta
+    };
+    let hue = (sector + offset + 360) % 360;
+    (hue as u16, saturation as u8, max as u8)
+}
+
+/// Convert a hue, saturation, and value to an RGB888 colour
+///
+/// This is the inverse of [`rgb888_to_hsv`]. Hues of 360 degrees and above wrap around.
+pub const fn hsv_to_rgb888(h: u16, s: u8, v: u8) -> (u8, u8, u8) {
+    if s == 0 {
+        return (v, v, v);
+    }
+    let h = h % 360;
+    let (s, v) = (s as u32, v as u32);
+    // How far the hue is through its 60 degree sector
+    let f = (h % 60) as u32;
+    let p = ((v * (255 - s) + 127) / 255) as u8;
+    let q = ((v * (255 * 60 - s * f) + 127 * 60) / (255 * 60)) as u8;
+    let t = ((v * (255 * 60 - s * (60 - f)) + 127 * 60) / (255 * 60)) as u8;
+    let v = v as u8;
+    match h / 60 {
+        0 => (v, t, p),
+        1 => (q, v, p),
+        2 => (p, v, t),
+        3 => (p, q, v),
+        4 => (t, p, v),
+        _ => (v, p, q),
+    }
+}
+
+const fn max3(a: i32, b: i32, c: i32) -> i32 {
+    let ab = if a > b { a } else { b };
+    if ab > c {
+        ab
+    } else {
+        c
+    }
+}
+
+const fn min3(a: i32, b: i32, c: i32) -> i32 {
+    let ab = if a < b { a } else { b };
+    if ab < c {
+        ab
+    } else {
+        c
+    }
+}
+
+/// 4x4 Bayer matrix, with the order in which pixels turn on as the brightness increases
+const BAYER_4X4: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// Convert a grayscale image into a 1bpp image using ordered dithering with a 4x4 Bayer matrix
+///
+/// gray - one brightness byte per pixel, row by row
+/// width - the number of pixels in each row
+/// out - the 1bpp rows, each padded to a whole number of bytes
+///
+/// Bit 0 of each byte is the leftmost pixel, the same as [`convert_1bpp_5bpp`] expects.
+/// Returns an error if the grayscale buffer is not made of whole rows, or if the output can not fit
+/// all the rows.
+#[allow(clippy::result_unit_err)]
+pub fn dither_ordered_4x4(gray: &[u8], width: usize, out: &mut [u8]) -> Result<(), ()> {
+    if width == 0 || !gray.len().is_multiple_of(width) {
+        return Err(());
+    }
+    let bytes_per_row = width.div_ceil(8);
+    let rows = gray.len() / width;
+    if out.len() < rows * bytes_per_row {
+        return Err(());
+    }
+    for (y, row) in gray.chunks_exact(width).enumerate() {
+        let out_row = &mut out[y * bytes_per_row..(y + 1) * bytes_per_row];
+        out_row.fill(0);
+        for (x, brightness) in row.iter().enumerate() {
+            // Thresholds are spread evenly over 0-255, at the middle of each of 16 steps
+            let threshold = BAYER_4X4[y % 4][x % 4] as u16 * 16 + 8;
+            if *brightness as u16 >= threshold {
+                out_row[x / 8] |= 1 << (x % 8);
+            }
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::conversion::colour::{convert_1bpp_5bpp, vflip_1bpp_const};
+    use crate::conversion::colour::{
+        convert_1bpp_5bpp, dither_ordered_4x4, hsv_to_rgb888, lerp_rgb565, luminance_rgb565,
+        luminance_rgb888, rgb888_to_hsv, vflip_1bpp_const,
+    };
     use std::format;
     use std::string::String;
     use std::vec::Vec;
@@ -110,7 +259,7 @@ mod tests {
 
     #[test]
     pub fn test_vflip_1bpp() {
-        let mut data: [u8; 4] = [0b1111_0000, 0b0000_1111, 0b1010_1010, 0b0101_0101];
+        let data: [u8; 4] = [0b1111_0000, 0b0000_1111, 0b1010_1010, 0b0101_0101];
         let data = vflip_1bpp_const(data);
         assert_eq!(
             data,
@@ -121,4 +270,97 @@ mod tests {
                 .collect::<Vec<String>>()
         );
     }
+
+    #[test]
+    pub fn test_lerp_rgb565() {
+        let red = 0xf800;
+        let blue = 0x001f;
+        let mixed = 0x5555;
+        for (a, b) in [(red, blue), (0x0000, 0xffff), (mixed, red)] {
+            assert_eq!(lerp_rgb565(a, b, 0), a);
+            assert_eq!(lerp_rgb565(a, b, 255), b);
+        }
+        assert_eq!(lerp_rgb565(0x0000, 0xffff, 128), 0x8410);
+        assert_eq!(lerp_rgb565(red, blue, 128), 0x7810);
+        assert_eq!(lerp_rgb565(mixed, mixed, 77), mixed);
+    }
+
+    #[test]
+    pub fn test_luminance() {
+        assert_eq!(luminance_rgb888(255, 0, 0), 76);
+        assert_eq!(luminance_rgb888(0, 255, 0), 150);
+        assert_eq!(luminance_rgb888(0, 0, 255), 29);
+        assert_eq!(luminance_rgb888(255, 255, 255), 255);
+        assert_eq!(luminance_rgb888(0, 0, 0), 0);
+        assert_eq!(luminance_rgb565(0xf800), 76);
+        assert_eq!(luminance_rgb565(0x07e0), 150);
+        assert_eq!(luminance_rgb565(0x001f), 29);
+        assert_eq!(luminance_rgb565(0xffff), 255);
+    }
+
+    #[test]
+    pub fn test_dither_gradient() {
+        let mut gray = [0u8; 32];
+        for (i, pixel) in gray.iter_mut().enumerate() {
+            *pixel = (i % 8) as u8 * 32;
+        }
+        let mut out = [0u8; 4];
+        assert_eq!(dither_ordered_4x4(&gray, 8, &mut out), Ok(()));
+        assert_eq!(out, [0b1111_0100, 0b1010_0000, 0b1101_0100, 0b1010_1000]);
+    }
+
+    #[test]
+    pub fn test_dither_sizes() {
+        // Black and white are solid, and partial rows are padded to a byte
+        let gray = [0, 255, 255, 0, 255, 255];
+        let mut out = [0xffu8; 2];
+        assert_eq!(dither_ordered_4x4(&gray, 3, &mut out), Ok(()));
+        assert_eq!(out, [0b110, 0b110]);
+        assert_eq!(dither_ordered_4x4(&gray, 4, &mut out), Err(()));
+        assert_eq!(dither_ordered_4x4(&gray, 0, &mut out), Err(()));
+        assert_eq!(dither_ordered_4x4(&gray, 3, &mut out[..1]), Err(()));
+    }
+
+    #[test]
+    pub fn test_hsv_primaries() {
+        assert_eq!(rgb888_to_hsv(255, 0, 0), (0, 255, 255));
+        assert_eq!(rgb888_to_hsv(0, 255, 0), (120, 255, 255));
+        assert_eq!(rgb888_to_hsv(0, 0, 255), (240, 255, 255));
+        assert_eq!(rgb888_to_hsv(255, 0, 255), (300, 255, 255));
+        assert_eq!(rgb888_to_hsv(128, 128, 128), (0, 0, 128));
+        assert_eq!(rgb888_to_hsv(0, 0, 0), (0, 0, 0));
+        assert_eq!(hsv_to_rgb888(0, 255, 255), (255, 0, 0));
+        assert_eq!(hsv_to_rgb888(120, 255, 255), (0, 255, 0));
+        assert_eq!(hsv_to_rgb888(240, 255, 255), (0, 0, 255));
+        assert_eq!(hsv_to_rgb888(60, 255, 128), (128, 128, 0));
+        assert_eq!(hsv_to_rgb888(480, 255, 255), (0, 255, 0));
+        assert_eq!(hsv_to_rgb888(200, 0, 77), (77, 77, 77));
+    }
+
+    #[test]
+    pub fn test_hsv_round_trip() {
+        // Fully saturated colours have one channel at 255 and one at 0
+        for level in 0..=255u8 {
+            for rgb in [
+                (255, level, 0),
+                (level, 255, 0),
+                (0, 255, level),
+                (0, level, 255),
+                (level, 0, 255),
+                (255, 0, level),
+            ] {
+                let hsv = rgb888_to_hsv(rgb.0, rgb.1, rgb.2);
+                let round_trip = hsv_to_rgb888(hsv.0, hsv.1, hsv.2);
+                // The hue only has 360 steps, so the varying channel can move slightly
+                for (a, b) in [
+                    (rgb.0, round_trip.0),
+                    (rgb.1, round_trip.1),
+                    (rgb.2, round_trip.2),
+                ] {
+                    assert!(a.abs_diff(b) <= 2, "{:?} became {:?}", rgb, round_trip);
+                }
+                assert_eq!(rgb888_to_hsv(round_trip.0, round_trip.1, round_trip.2), hsv);
+            }
+        }
+    }
 }
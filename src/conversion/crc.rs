@@ -0,0 +1,78 @@
+//! Cyclic redundancy checks, for detecting corrupted data such as structures stored in flash
+//!
+//! These are computed bit by bit, so they do not need a lookup table in memory.
+
+/// Calculate the CRC-16/CCITT-FALSE checksum of some data
+///
+/// This uses the polynomial 0x1021 with an initial value of 0xFFFF, and no reflection or final XOR.
+pub const fn crc16(data: &[u8]) -> u16 {
+    let mut crc = 0xffffu16;
+    let mut i = 0;
+    while i < data.len() {
+        crc ^= (data[i] as u16) << 8;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+            bit += 1;
+        }
+        i += 1;
+    }
+    crc
+}
+
+/// Calculate the CRC-32 checksum of some data, as used by Ethernet, zip, and png
+///
+/// This uses the reflected polynomial 0xEDB88320 with an initial value and final XOR of 0xFFFFFFFF.
+pub const fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+    let mut i = 0;
+    while i < data.len() {
+        crc ^= data[i] as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        i += 1;
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conversion::crc::{crc16, crc32};
+
+    #[test]
+    pub fn test_crc16() {
+        assert_eq!(crc16(b"123456789"), 0x29b1);
+        assert_eq!(crc16(b""), 0xffff);
+        assert_eq!(crc16(b"A"), 0xb915);
+    }
+
+    #[test]
+    pub fn test_crc32() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(
+            crc32(b"The quick brown fox jumps over the lazy dog"),
+            0x414f_a339
+        );
+    }
+
+    #[test]
+    pub fn detects_corruption() {
+        let mut data = *b"stored structure";
+        let (crc16_before, crc32_before) = (crc16(&data), crc32(&data));
+        data[3] ^= 0b0000_0100;
+        assert_ne!(crc16(&data), crc16_before);
+        assert_ne!(crc32(&data), crc32_before);
+    }
+}
@@ -0,0 +1,127 @@
+//! Functions to render numbers as text without `format!` or an allocator
+//!
+//! The output is written into a fixed capacity [`ArrayString`] that is large enough for any input.
+
+use arrayvec::ArrayString;
+use core::cmp::min;
+
+/// The most decimals [`fmt_f32`] will render, as an f32 has no more precision than this
+pub const MAX_F32_DECIMALS: usize = 7;
+
+/// Render an integer in decimal
+///
+/// ```
+/// use nostd_structs::conversion::fmt::fmt_i32;
+/// assert_eq!(fmt_i32(-1234).as_str(), "-1234");
+/// ```
+pub fn fmt_i32(n: i32) -> ArrayString<16> {
+    let mut out = ArrayString::new();
+    if n < 0 {
+        out.push('-');
+    }
+    push_digits(&mut out, n.unsigned_abs() as u128, 1);
+    out
+}
+
+/// Render a float in decimal with a fixed number of decimals
+///
+/// The value is rounded to the nearest decimal, with halves rounded away from zero. The decimals
+/// are limited to [`MAX_F32_DECIMALS`]. A value that rounds to zero is rendered without a sign.
+/// NaN and infinities are rendered as `NaN`, `inf`, and `-inf`.
+///
+/// The capacity fits the largest f32, which has 39 digits before the decimal point.
+///
+/// ```
+/// use nostd_structs::conversion::fmt::fmt_f32;
+/// assert_eq!(fmt_f32(-1.23456, 2).as_str(), "-1.23");
+/// ```
+pub fn fmt_f32(n: f32, decimals: usize) -> ArrayString<48> {
+    let mut out = ArrayString::new();
+    if n.is_nan() {
+        out.push_str("NaN");
+        return out;
+    }
+    if n.is_infinite() {
+        out.push_str(if n < 0.0 { "-inf" } else { "inf" });
+        return out;
+    }
+    let decimals = min(decimals, MAX_F32_DECIMALS);
+    // Widen before scaling, so the fraction keeps all the precision of the f32
+    let abs = if n < 0.0 { -(n as f64) } else { n as f64 };
+    let mut scale = 1u64;
+    for _ in 0..decimals {
+        scale *= 10;
+    }
+    let mut int_part = abs as u128;
+    let mut frac_part = ((abs - int_part as f64) * scale as f64 + 0.5) as u64;
+    // Rounding the fraction up can carry into the integer part, such as 0.999 to 1.00
+    if frac_part >= scale {
+        int_part += 1;
+        frac_part -= scale;
+    }
+    if n < 0.0 && (int_part != 0 || frac_part != 0) {
+        out.push('-');
+    }
+    push_digits(&mut out, int_part, 1);
+    if decimals > 0 {
+        out.push('.');
+        push_digits(&mut out, frac_part as u128, decimals);
+    }
+    out
+}
+
+/// Push the digits of a value, left padded with zeros to at least min_width digits
+fn push_digits<const N: usize>(out: &mut ArrayString<N>, mut value: u128, min_width: usize) {
+    // u128::MAX has 39 digits
+    let mut digits = [0u8; 39];
+    let mut count = 0;
+    while value > 0 || count < min_width {
+        digits[digits.len() - 1 - count] = b'0' + (value % 10) as u8;
+        value /= 10;
+        count += 1;
+    }
+    for &digit in &digits[digits.len() - count..] {
+        out.push(digit as char);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conversion::fmt::{fmt_f32, fmt_i32};
+
+    #[test]
+    pub fn test_fmt_i32() {
+        assert_eq!(fmt_i32(0).as_str(), "0");
+        assert_eq!(fmt_i32(7).as_str(), "7");
+        assert_eq!(fmt_i32(-42).as_str(), "-42");
+        assert_eq!(fmt_i32(1_000_000).as_str(), "1000000");
+        assert_eq!(fmt_i32(i32::MAX).as_str(), "2147483647");
+        assert_eq!(fmt_i32(i32::MIN).as_str(), "-2147483648");
+    }
+
+    #[test]
+    pub fn test_fmt_f32() {
+        assert_eq!(fmt_f32(0.0, 2).as_str(), "0.00");
+        assert_eq!(fmt_f32(1.23456, 0).as_str(), "1");
+        assert_eq!(fmt_f32(1.23456, 2).as_str(), "1.23");
+        assert_eq!(fmt_f32(-1.23456, 3).as_str(), "-1.235");
+        assert_eq!(fmt_f32(0.05, 2).as_str(), "0.05");
+        assert_eq!(fmt_f32(-0.5, 1).as_str(), "-0.5");
+        assert_eq!(fmt_f32(1e10, 1).as_str(), "10000000000.0");
+        assert_eq!(fmt_f32(f32::NAN, 2).as_str(), "NaN");
+        assert_eq!(fmt_f32(f32::NEG_INFINITY, 2).as_str(), "-inf");
+        assert_eq!(fmt_f32(f32::MAX, 7).len(), 39 + 1 + 7);
+    }
+
+    #[test]
+    pub fn test_fmt_f32_rounding() {
+        assert_eq!(fmt_f32(2.5, 0).as_str(), "3");
+        assert_eq!(fmt_f32(-1.25, 1).as_str(), "-1.3");
+        assert_eq!(fmt_f32(0.999, 2).as_str(), "1.00");
+        assert_eq!(fmt_f32(9.96, 1).as_str(), "10.0");
+        // Rounds to zero, so the sign is dropped
+        assert_eq!(fmt_f32(-0.001, 2).as_str(), "0.00");
+        // Decimals beyond the precision of an f32 are not rendered
+        assert_eq!(fmt_f32(1.5, 20).as_str(), "1.5000000");
+    }
+}
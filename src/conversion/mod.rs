@@ -1,3 +1,6 @@
 //! Conversion tools for converting various formats and structures
 
 pub mod colour;
+pub mod crc;
+pub mod fmt;
+pub mod rle;
@@ -0,0 +1,100 @@
+//! Run-length encoding, for compressing data with long runs of the same byte, such as 1bpp tiles
+//!
+//! The encoding is a sequence of pairs of a count (1 to 255) followed by the byte that is repeated.
+//! Data without runs doubles in size, so make sure the output buffer is large enough.
+
+/// Encode the input into the output buffer, returning the number of bytes written
+///
+/// Returns an error if the output buffer is too small, in which case its contents are unspecified.
+#[allow(clippy::result_unit_err)]
+pub fn rle_encode(input: &[u8], out: &mut [u8]) -> Result<usize, ()> {
+    let mut written = 0;
+    let mut i = 0;
+    while i < input.len() {
+        let byte = input[i];
+        let mut count = 1;
+        while count < u8::MAX as usize && i + count < input.len() && input[i + count] == byte {
+            count += 1;
+        }
+        if written + 2 > out.len() {
+            return Err(());
+        }
+        out[written] = count as u8;
+        out[written + 1] = byte;
+        written += 2;
+        i += count;
+    }
+    Ok(written)
+}
+
+/// Decode the input into the output buffer, returning the number of bytes written
+///
+/// Returns an error if the output buffer is too small, or if the input is not valid encoded data.
+#[allow(clippy::result_unit_err)]
+pub fn rle_decode(input: &[u8], out: &mut [u8]) -> Result<usize, ()> {
+    if !input.len().is_multiple_of(2) {
+        return Err(());
+    }
+    let mut written = 0;
+    for pair in input.chunks_exact(2) {
+        let count = pair[0] as usize;
+        if count == 0 || written + count > out.len() {
+            return Err(());
+        }
+        out[written..written + count].fill(pair[1]);
+        written += count;
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conversion::rle::{rle_decode, rle_encode};
+
+    #[test]
+    pub fn round_trips_runs() {
+        let mut input = [0u8; 300];
+        input[100..110].fill(0xff);
+        input[299] = 0x18;
+        let mut encoded = [0u8; 16];
+        let encoded_len = rle_encode(&input, &mut encoded).unwrap();
+        // 100 zeros, 10 0xff, 189 zeros, then 0x18
+        assert_eq!(
+            &encoded[..encoded_len],
+            &[100, 0x00, 10, 0xff, 189, 0x00, 1, 0x18]
+        );
+        let mut decoded = [0u8; 300];
+        assert_eq!(rle_decode(&encoded[..encoded_len], &mut decoded), Ok(300));
+        assert_eq!(decoded, input);
+    }
+
+    #[test]
+    pub fn splits_long_runs() {
+        let input = [7u8; 600];
+        let mut encoded = [0u8; 6];
+        assert_eq!(rle_encode(&input, &mut encoded), Ok(6));
+        assert_eq!(encoded, [255, 7, 255, 7, 90, 7]);
+    }
+
+    #[test]
+    pub fn incompressible_data() {
+        let input: [u8; 8] = [1, 2, 3, 4, 5, 6, 7, 8];
+        let mut too_small = [0u8; 15];
+        assert_eq!(rle_encode(&input, &mut too_small), Err(()));
+        let mut encoded = [0u8; 16];
+        assert_eq!(rle_encode(&input, &mut encoded), Ok(16));
+        let mut decoded = [0u8; 8];
+        assert_eq!(rle_decode(&encoded, &mut decoded), Ok(8));
+        assert_eq!(decoded, input);
+        let mut decoded_too_small = [0u8; 7];
+        assert_eq!(rle_decode(&encoded, &mut decoded_too_small), Err(()));
+    }
+
+    #[test]
+    pub fn rejects_invalid_input() {
+        let mut out = [0u8; 8];
+        assert_eq!(rle_decode(&[2, 1, 3], &mut out), Err(()));
+        assert_eq!(rle_decode(&[0, 1], &mut out), Err(()));
+        assert_eq!(rle_decode(&[], &mut out), Ok(0));
+    }
+}
@@ -0,0 +1,99 @@
+use crate::algos::math::cos;
+use core::f32::consts::TAU;
+
+/// The Goertzel filter measures how strongly one frequency is present in a stream of samples,
+/// such as to detect DTMF tones or beeps, without computing a full FFT.
+///
+/// Samples are processed in windows of **N** samples. A longer window can tell closer frequencies
+/// apart, but takes longer to fill. Each sample costs one multiplication and two additions.
+pub struct Goertzel<const N: usize> {
+    coefficient: f32,
+    s1: f32,
+    s2: f32,
+    count: usize,
+    magnitude_squared: f32,
+}
+
+impl<const N: usize> Goertzel<N> {
+    /// Create a filter for a target frequency, at a sample rate in the same unit (such as Hz)
+    pub fn new(target_frequency: f32, sample_rate: f32) -> Self {
+        assert!(
+            N > 0,
+            "A Goertzel filter needs a window of at least one sample"
+        );
+        Goertzel {
+            coefficient: 2.0 * cos(TAU * target_frequency / sample_rate),
+            s1: 0.0,
+            s2: 0.0,
+            count: 0,
+            magnitude_squared: 0.0,
+        }
+    }
+
+    /// Add a sample. Once the window is full, the magnitude is updated and a new window starts.
+    pub fn push(&mut self, sample: f32) {
+        let s0 = sample + self.coefficient * self.s1 - self.s2;
+        self.s2 = self.s1;
+        self.s1 = s0;
+        self.count += 1;
+        if self.count == N {
+            self.magnitude_squared =
+                self.s1 * self.s1 + self.s2 * self.s2 - self.coefficient * self.s1 * self.s2;
+            self.s1 = 0.0;
+            self.s2 = 0.0;
+            self.count = 0;
+        }
+    }
+
+    /// Returns the squared magnitude of the target frequency over the last full window, or 0 if no
+    /// window has been completed yet
+    ///
+    /// A sine wave of amplitude A at the target frequency gives about (A * N / 2)^2.
+    pub fn magnitude_squared(&self) -> f32 {
+        self.magnitude_squared
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::structs::dsp::Goertzel;
+
+    fn detect(frequency: f32) -> f32 {
+        let mut filter: Goertzel<205> = Goertzel::new(697.0, 8000.0);
+        for i in 0..205 {
+            let t = i as f32 / 8000.0;
+            filter.push((core::f32::consts::TAU * frequency * t).sin());
+        }
+        filter.magnitude_squared()
+    }
+
+    #[test]
+    fn detects_target_frequency() {
+        let on_target = detect(697.0);
+        let expected = (205.0f32 / 2.0) * (205.0 / 2.0);
+        assert!(
+            (on_target - expected).abs() < expected * 0.05,
+            "{}",
+            on_target
+        );
+        for off_target in [770.0, 941.0, 1209.0] {
+            assert!(detect(off_target) < on_target / 100.0, "{}", off_target);
+        }
+    }
+
+    #[test]
+    fn magnitude_updates_per_window() {
+        let mut filter: Goertzel<4> = Goertzel::new(1.0, 4.0);
+        for sample in [1.0, 0.0, -1.0] {
+            filter.push(sample);
+        }
+        assert_eq!(filter.magnitude_squared(), 0.0);
+        filter.push(0.0);
+        assert!((filter.magnitude_squared() - 4.0).abs() < 1e-4);
+        // Silence in the next window clears it
+        for _ in 0..4 {
+            filter.push(0.0);
+        }
+        assert_eq!(filter.magnitude_squared(), 0.0);
+    }
+}
@@ -0,0 +1,5 @@
+//! Signal processing over streams of samples, such as detecting tones
+
+mod goertzel;
+
+pub use goertzel::Goertzel;
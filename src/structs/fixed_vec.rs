@@ -0,0 +1,148 @@
+use arrayvec::ArrayVec;
+use core::cmp::Ordering;
+use core::ops::{Deref, DerefMut};
+
+/// The FixedVec is a vector with a fixed capacity **S**, stored inline without a heap.
+///
+/// It dereferences to a slice, so all the slice methods are available, and adds helpers for
+/// common patterns when the capacity runs out.
+pub struct FixedVec<T, const S: usize> {
+    data: ArrayVec<T, S>,
+}
+
+impl<T, const S: usize> FixedVec<T, S> {
+    /// Create a new, empty FixedVec
+    pub const fn new() -> Self {
+        FixedVec {
+            data: ArrayVec::new_const(),
+        }
+    }
+
+    /// Append an item to the end. If the capacity has been reached, the item is returned as an error.
+    pub fn push(&mut self, item: T) -> Result<(), T> {
+        self.data.try_push(item).map_err(|e| e.element())
+    }
+
+    /// Remove the last item
+    pub fn pop(&mut self) -> Option<T> {
+        self.data.pop()
+    }
+
+    /// Append an item to the end, removing and returning the first (oldest) item if the capacity
+    /// has been reached
+    pub fn push_or_evict_oldest(&mut self, item: T) -> Option<T> {
+        let mut evicted = None;
+        if self.data.is_full() {
+            if S == 0 {
+                return Some(item);
+            }
+            evicted = Some(self.data.remove(0));
+        }
+        self.data.push(item);
+        evicted
+    }
+
+    /// Insert an item after all the items that are less than or equal to it, so that a FixedVec
+    /// that is sorted stays sorted. If the capacity has been reached, the item is returned as an error.
+    pub fn insert_sorted_by<F: FnMut(&T, &T) -> Ordering>(
+        &mut self,
+        item: T,
+        mut compare: F,
+    ) -> Result<(), T> {
+        if self.data.is_full() {
+            return Err(item);
+        }
+        let index = self
+            .data
+            .iter()
+            .position(|existing| compare(existing, &item) == Ordering::Greater)
+            .unwrap_or(self.data.len());
+        self.data.insert(index, item);
+        Ok(())
+    }
+
+    /// Remove the first item that matches the predicate, by replacing it with the last item
+    ///
+    /// This does not preserve the order, but does not need to move the other items.
+    pub fn swap_remove_where<F: FnMut(&T) -> bool>(&mut self, predicate: F) -> Option<T> {
+        let index = self.data.iter().position(predicate)?;
+        Some(self.data.swap_remove(index))
+    }
+
+    /// Returns the capacity of the FixedVec
+    pub fn capacity(&self) -> usize {
+        S
+    }
+
+    /// Returns true if the capacity has been reached
+    pub fn is_full(&self) -> bool {
+        self.data.is_full()
+    }
+}
+
+impl<T, const S: usize> Default for FixedVec<T, S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const S: usize> Deref for FixedVec<T, S> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+
+impl<T, const S: usize> DerefMut for FixedVec<T, S> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.data
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::structs::fixed_vec::FixedVec;
+
+    #[test]
+    pub fn insert_sorted_keeps_order() {
+        let mut vec: FixedVec<i32, 5> = FixedVec::new();
+        for item in [5, 1, 4, 1, 3] {
+            vec.insert_sorted_by(item, |a, b| a.cmp(b)).unwrap();
+        }
+        assert_eq!(&*vec, &[1, 1, 3, 4, 5]);
+        assert_eq!(vec.insert_sorted_by(2, |a, b| a.cmp(b)), Err(2));
+        // Equal items go after the existing ones
+        let mut pairs: FixedVec<(i32, char), 3> = FixedVec::new();
+        for pair in [(1, 'a'), (0, 'b'), (1, 'c')] {
+            pairs.insert_sorted_by(pair, |a, b| a.0.cmp(&b.0)).unwrap();
+        }
+        assert_eq!(&*pairs, &[(0, 'b'), (1, 'a'), (1, 'c')]);
+    }
+
+    #[test]
+    pub fn evicts_oldest_at_capacity() {
+        let mut vec: FixedVec<i32, 3> = FixedVec::new();
+        assert_eq!(vec.push_or_evict_oldest(1), None);
+        assert_eq!(vec.push_or_evict_oldest(2), None);
+        assert_eq!(vec.push_or_evict_oldest(3), None);
+        assert!(vec.is_full());
+        assert_eq!(vec.push_or_evict_oldest(4), Some(1));
+        assert_eq!(vec.push_or_evict_oldest(5), Some(2));
+        assert_eq!(&*vec, &[3, 4, 5]);
+        assert_eq!(vec.push(6), Err(6));
+    }
+
+    #[test]
+    pub fn swap_removes_matching() {
+        let mut vec: FixedVec<i32, 4> = FixedVec::new();
+        for item in [10, 20, 30, 40] {
+            vec.push(item).unwrap();
+        }
+        assert_eq!(vec.swap_remove_where(|item| *item > 15), Some(20));
+        assert_eq!(&*vec, &[10, 40, 30]);
+        assert_eq!(vec.swap_remove_where(|item| *item > 100), None);
+        vec[0] = 11;
+        assert_eq!(vec.first(), Some(&11));
+    }
+}
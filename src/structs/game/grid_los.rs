@@ -0,0 +1,80 @@
+/// Check if there is a clear line of sight between two tiles on a grid
+///
+/// The tiles along the line are found with Bresenham's line algorithm, and the function returns
+/// false at the first one that is blocked. The start and end tiles themselves are not checked, so
+/// a wall can be seen, but not seen through.
+///
+/// ```
+/// use nostd_structs::structs::game::grid_line_of_sight;
+/// let wall_at_x_2 = |x: i32, _y: i32| x == 2;
+/// assert!(grid_line_of_sight(0, 0, 1, 5, wall_at_x_2));
+/// assert!(!grid_line_of_sight(0, 0, 4, 1, wall_at_x_2));
+/// ```
+pub fn grid_line_of_sight<F: Fn(i32, i32) -> bool>(
+    x0: i32,
+    y0: i32,
+    x1: i32,
+    y1: i32,
+    is_blocked: F,
+) -> bool {
+    let dx = (x1 - x0).abs();
+    let dy = -(y1 - y0).abs();
+    let step_x = if x0 < x1 { 1 } else { -1 };
+    let step_y = if y0 < y1 { 1 } else { -1 };
+    let mut err = dx + dy;
+    let (mut x, mut y) = (x0, y0);
+    loop {
+        if (x, y) == (x1, y1) {
+            return true;
+        }
+        if (x, y) != (x0, y0) && is_blocked(x, y) {
+            return false;
+        }
+        let doubled = 2 * err;
+        if doubled >= dy {
+            err += dy;
+            x += step_x;
+        }
+        if doubled <= dx {
+            err += dx;
+            y += step_y;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::structs::game::grid_line_of_sight;
+    use std::cell::RefCell;
+    use std::vec::Vec;
+
+    #[test]
+    fn clear_line() {
+        assert!(grid_line_of_sight(0, 0, 10, 3, |_x, _y| false));
+        assert!(grid_line_of_sight(3, 3, 3, 3, |_x, _y| true));
+        // Neighbors can always see each other
+        assert!(grid_line_of_sight(3, 3, 4, 4, |_x, _y| true));
+    }
+
+    #[test]
+    fn blocked_line() {
+        let wall = |x: i32, y: i32| (x, y) == (5, 0);
+        assert!(!grid_line_of_sight(0, 0, 10, 0, wall));
+        assert!(!grid_line_of_sight(10, 0, 0, 0, wall));
+        // The wall itself is visible
+        assert!(grid_line_of_sight(0, 0, 5, 0, wall));
+        assert!(grid_line_of_sight(0, 1, 10, 1, wall));
+    }
+
+    #[test]
+    fn diagonal_lines() {
+        let visited = RefCell::new(Vec::new());
+        assert!(grid_line_of_sight(0, 0, -4, 4, |x, y| {
+            visited.borrow_mut().push((x, y));
+            false
+        }));
+        assert_eq!(*visited.borrow(), [(-1, 1), (-2, 2), (-3, 3)]);
+        assert!(!grid_line_of_sight(0, 0, 4, 4, |x, y| (x, y) == (2, 2)));
+        assert!(grid_line_of_sight(0, 0, 4, 4, |x, y| (x, y) == (2, 1)));
+    }
+}
@@ -0,0 +1,5 @@
+//! Helpers that are common in games, such as visibility on tile maps
+
+mod grid_los;
+
+pub use grid_los::grid_line_of_sight;
@@ -0,0 +1,214 @@
+use arrayvec::ArrayVec;
+use core::cmp::Ordering;
+
+/// The IndexedMinHeap is a priority queue of node ids, where the priority of a node that is
+/// already queued can be lowered, such as when a shorter path is found in A* or Dijkstra.
+///
+/// Node ids must be below the capacity **S**, as they are used to index the position of each node
+/// in the heap. Nodes with equal priority are popped in the order they were pushed.
+pub struct IndexedMinHeap<P: PartialOrd + Copy, const S: usize> {
+    /// Tuples of node id, priority, and push order
+    heap: ArrayVec<(usize, P, usize), S>,
+    /// The index in the heap of each node id, if it is queued
+    positions: [Option<usize>; S],
+    next_order: usize,
+}
+
+impl<P: PartialOrd + Copy, const S: usize> IndexedMinHeap<P, S> {
+    /// Create a new, empty heap
+    pub const fn new() -> Self {
+        IndexedMinHeap {
+            heap: ArrayVec::new_const(),
+            positions: [None; S],
+            next_order: 0,
+        }
+    }
+
+    /// Queue a node with a priority. If the node is already queued, its priority is replaced.
+    ///
+    /// Panics if the id is not below the capacity.
+    pub fn push(&mut self, id: usize, priority: P) {
+        assert!(id < S, "Node id {id} must be below the capacity {S}");
+        match self.positions[id] {
+            Some(index) => {
+                self.heap[index].1 = priority;
+                let index = self.sift_up(index);
+                self.sift_down(index);
+            }
+            None => {
+                let order = self.next_order;
+                self.next_order += 1;
+                self.heap.push((id, priority, order));
+                self.positions[id] = Some(self.heap.len() - 1);
+                self.sift_up(self.heap.len() - 1);
+            }
+        }
+    }
+
+    /// Remove and return the node with the lowest priority
+    pub fn pop_min(&mut self) -> Option<usize> {
+        if self.heap.is_empty() {
+            return None;
+        }
+        let last = self.heap.len() - 1;
+        self.swap(0, last);
+        let (id, _priority, _order) = self.heap.pop()?;
+        self.positions[id] = None;
+        if !self.heap.is_empty() {
+            self.sift_down(0);
+        }
+        Some(id)
+    }
+
+    /// Returns the node with the lowest priority, and its priority, without removing it
+    pub fn peek_min(&self) -> Option<(usize, P)> {
+        self.heap
+            .first()
+            .map(|(id, priority, _order)| (*id, *priority))
+    }
+
+    /// Lower the priority of a queued node
+    ///
+    /// Returns false, and changes nothing, if the node is not queued or the new priority is not lower.
+    pub fn decrease_priority(&mut self, id: usize, new: P) -> bool {
+        let index = match self.positions.get(id) {
+            Some(Some(index)) => *index,
+            _ => return false,
+        };
+        if new.partial_cmp(&self.heap[index].1) != Some(Ordering::Less) {
+            return false;
+        }
+        self.heap[index].1 = new;
+        self.sift_up(index);
+        true
+    }
+
+    /// Returns the priority of a node, if it is queued
+    pub fn priority(&self, id: usize) -> Option<P> {
+        let index = (*self.positions.get(id)?)?;
+        Some(self.heap[index].1)
+    }
+
+    /// Returns true if the node is queued
+    pub fn contains(&self, id: usize) -> bool {
+        matches!(self.positions.get(id), Some(Some(_)))
+    }
+
+    /// Returns the number of queued nodes
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Returns true if there are no queued nodes
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Returns true if the entry at index a should be popped before the entry at index b
+    fn before(&self, a: usize, b: usize) -> bool {
+        let (_, a_priority, a_order) = self.heap[a];
+        let (_, b_priority, b_order) = self.heap[b];
+        match a_priority.partial_cmp(&b_priority) {
+            Some(Ordering::Less) => true,
+            Some(Ordering::Greater) => false,
+            // Equal, or incomparable such as NaN, so fall back to the push order
+            _ => a_order < b_order,
+        }
+    }
+
+    fn swap(&mut self, a: usize, b: usize) {
+        self.heap.swap(a, b);
+        self.positions[self.heap[a].0] = Some(a);
+        self.positions[self.heap[b].0] = Some(b);
+    }
+
+    /// Move an entry up until its parent comes before it, returning its new index
+    fn sift_up(&mut self, mut index: usize) -> usize {
+        while index > 0 {
+            let parent = (index - 1) / 2;
+            if !self.before(index, parent) {
+                break;
+            }
+            self.swap(index, parent);
+            index = parent;
+        }
+        index
+    }
+
+    /// Move an entry down until it comes before both its children
+    fn sift_down(&mut self, mut index: usize) {
+        loop {
+            let mut first = index;
+            for child in [2 * index + 1, 2 * index + 2] {
+                if child < self.heap.len() && self.before(child, first) {
+                    first = child;
+                }
+            }
+            if first == index {
+                return;
+            }
+            self.swap(index, first);
+            index = first;
+        }
+    }
+}
+
+impl<P: PartialOrd + Copy, const S: usize> Default for IndexedMinHeap<P, S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::structs::indexed_heap::IndexedMinHeap;
+    use std::vec::Vec;
+
+    fn drain<const S: usize>(heap: &mut IndexedMinHeap<u32, S>) -> Vec<usize> {
+        let mut popped = Vec::new();
+        while let Some(id) = heap.pop_min() {
+            popped.push(id);
+        }
+        popped
+    }
+
+    #[test]
+    pub fn pops_in_priority_order() {
+        let mut heap: IndexedMinHeap<u32, 8> = IndexedMinHeap::new();
+        for (id, priority) in [(0, 50), (1, 10), (2, 40), (3, 20), (4, 30)] {
+            heap.push(id, priority);
+        }
+        assert_eq!(heap.peek_min(), Some((1, 10)));
+        assert_eq!(drain(&mut heap), [1, 3, 4, 2, 0]);
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    pub fn decrease_priority_reorders() {
+        let mut heap: IndexedMinHeap<u32, 8> = IndexedMinHeap::new();
+        for (id, priority) in [(0, 50), (1, 10), (2, 40), (3, 20)] {
+            heap.push(id, priority);
+        }
+        assert!(heap.decrease_priority(0, 5));
+        assert!(!heap.decrease_priority(2, 45));
+        assert!(!heap.decrease_priority(7, 1));
+        assert_eq!(heap.priority(0), Some(5));
+        assert_eq!(heap.pop_min(), Some(0));
+        assert!(!heap.contains(0));
+        assert!(!heap.decrease_priority(0, 1));
+        // Pushing a queued node replaces its priority, even if it is higher
+        heap.push(1, 30);
+        assert_eq!(heap.len(), 3);
+        assert_eq!(drain(&mut heap), [3, 1, 2]);
+    }
+
+    #[test]
+    pub fn equal_priorities_are_stable() {
+        let mut heap: IndexedMinHeap<u32, 8> = IndexedMinHeap::new();
+        for id in [4, 2, 6, 0, 1] {
+            heap.push(id, 7);
+        }
+        heap.push(5, 3);
+        assert_eq!(drain(&mut heap), [5, 4, 2, 6, 0, 1]);
+    }
+}
@@ -0,0 +1,232 @@
+use crate::algos::slice::select_nth_by;
+use crate::structs::NDimensionalPoint;
+use arrayvec::ArrayVec;
+use core::cmp::Ordering;
+
+type Point2D = NDimensionalPoint<f32, f32, 2>;
+
+/// The KdTree2D is an index of up to **S** points with values, for finding the points nearest to
+/// a query point.
+///
+/// The tree is built once from a slice, by splitting the points at the median along x, then y,
+/// and so on. The nodes are stored in a fixed array, with each node in the middle of the range of
+/// its children, so there are no pointers and the tree is always balanced. Searches recurse once
+/// per level, which is at most log2(**S**) + 1 levels.
+///
+/// ```
+/// use nostd_structs::structs::{KdTree2D, NDimensionalPoint};
+/// let towns = [
+///     (NDimensionalPoint::new([0.0, 0.0]), "origin"),
+///     (NDimensionalPoint::new([5.0, 1.0]), "east"),
+///     (NDimensionalPoint::new([-1.0, 6.0]), "north"),
+/// ];
+/// let tree: KdTree2D<&str, 4> = KdTree2D::new(&towns).unwrap();
+/// assert_eq!(tree.nearest(&NDimensionalPoint::new([4.0, 0.0])), Some(&"east"));
+/// ```
+pub struct KdTree2D<V, const S: usize> {
+    nodes: ArrayVec<(Point2D, V), S>,
+}
+
+impl<V: Clone, const S: usize> KdTree2D<V, S> {
+    /// Build a tree from a slice of points and their values
+    ///
+    /// Returns an error if there are more than **S** points.
+    #[allow(clippy::result_unit_err)]
+    pub fn new(points: &[(Point2D, V)]) -> Result<Self, ()> {
+        if points.len() > S {
+            return Err(());
+        }
+        let mut nodes: ArrayVec<(Point2D, V), S> = points.iter().cloned().collect();
+        split(&mut nodes, 0);
+        Ok(KdTree2D { nodes })
+    }
+}
+
+impl<V, const S: usize> KdTree2D<V, S> {
+    /// Returns the value of the point nearest to the query, or None if the tree is empty
+    ///
+    /// If several points are equally near, any one of them may be returned.
+    pub fn nearest(&self, query: &Point2D) -> Option<&V> {
+        let mut best: Option<(usize, f32)> = None;
+        self.search_nearest(0, self.nodes.len(), 0, query, &mut best);
+        best.map(|(index, _distance)| &self.nodes[index].1)
+    }
+
+    /// Call a function with every point, and its value, that is within a radius of the query
+    /// (inclusive)
+    ///
+    /// The points are visited in no particular order.
+    pub fn within_radius<F: FnMut(&Point2D, &V)>(&self, query: &Point2D, radius: f32, mut f: F) {
+        self.search_radius(0, self.nodes.len(), 0, query, radius * radius, &mut f);
+    }
+
+    /// Returns the number of points in the tree
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns true if the tree has no points
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    fn search_nearest(
+        &self,
+        start: usize,
+        end: usize,
+        axis: usize,
+        query: &Point2D,
+        best: &mut Option<(usize, f32)>,
+    ) {
+        if start >= end {
+            return;
+        }
+        let middle = start + (end - start - 1) / 2;
+        let point = &self.nodes[middle].0;
+        let distance = distance_squared(point, query);
+        if best.is_none_or(|(_index, best_distance)| distance < best_distance) {
+            *best = Some((middle, distance));
+        }
+        // Search the side of the split the query is on first, as it most likely has the nearest
+        // point, then only search the other side if it could be nearer than the best so far
+        let offset = query.dimension(axis) - point.dimension(axis);
+        let (near, far) = if offset < 0.0 {
+            ((start, middle), (middle + 1, end))
+        } else {
+            ((middle + 1, end), (start, middle))
+        };
+        self.search_nearest(near.0, near.1, 1 - axis, query, best);
+        if best.is_none_or(|(_index, best_distance)| offset * offset < best_distance) {
+            self.search_nearest(far.0, far.1, 1 - axis, query, best);
+        }
+    }
+
+    fn search_radius<F: FnMut(&Point2D, &V)>(
+        &self,
+        start: usize,
+        end: usize,
+        axis: usize,
+        query: &Point2D,
+        radius_squared: f32,
+        f: &mut F,
+    ) {
+        if start >= end {
+            return;
+        }
+        let middle = start + (end - start - 1) / 2;
+        let (point, value) = &self.nodes[middle];
+        if distance_squared(point, query) <= radius_squared {
+            f(point, value);
+        }
+        let offset = query.dimension(axis) - point.dimension(axis);
+        // Points on the lower side are not greater along the axis, and points on the upper side
+        // are not less, so a side only needs searching if the circle reaches the split
+        if offset <= 0.0 || offset * offset <= radius_squared {
+            self.search_radius(start, middle, 1 - axis, query, radius_squared, f);
+        }
+        if offset >= 0.0 || offset * offset <= radius_squared {
+            self.search_radius(middle + 1, end, 1 - axis, query, radius_squared, f);
+        }
+    }
+}
+
+/// Reorder the nodes so that the median along the axis is in the middle, with the nodes that are
+/// not greater before it and the nodes that are not less after it, then split each side
+fn split<V>(nodes: &mut [(Point2D, V)], axis: usize) {
+    if nodes.len() <= 1 {
+        return;
+    }
+    let middle = (nodes.len() - 1) / 2;
+    select_nth_by(nodes, middle, |a, b| {
+        a.0.dimension(axis)
+            .partial_cmp(b.0.dimension(axis))
+            .unwrap_or(Ordering::Equal)
+    });
+    let (lower, upper) = nodes.split_at_mut(middle);
+    split(lower, 1 - axis);
+    split(&mut upper[1..], 1 - axis);
+}
+
+fn distance_squared(a: &Point2D, b: &Point2D) -> f32 {
+    let dx = a.dimension(0) - b.dimension(0);
+    let dy = a.dimension(1) - b.dimension(1);
+    dx * dx + dy * dy
+}
+
+#[cfg(test)]
+mod test {
+    use crate::algos::rand::lcg::LcgRng;
+    use crate::algos::rand::RandomNumberGenerator;
+    use crate::structs::kdtree::{distance_squared, KdTree2D, Point2D};
+    use crate::structs::NDimensionalPoint;
+    use std::collections::BTreeSet;
+    use std::vec::Vec;
+
+    fn random_point(rng: &mut LcgRng) -> Point2D {
+        NDimensionalPoint::new([rng.next_f32() * 100.0 - 50.0, rng.next_f32() * 100.0 - 50.0])
+    }
+
+    fn random_points(seed: u64, count: usize) -> Vec<(Point2D, usize)> {
+        let mut rng = LcgRng::new(seed);
+        (0..count).map(|id| (random_point(&mut rng), id)).collect()
+    }
+
+    #[test]
+    pub fn nearest_matches_brute_force() {
+        for (seed, count) in [(1, 1), (2, 2), (3, 7), (4, 64), (5, 100)] {
+            let points = random_points(seed, count);
+            let tree: KdTree2D<usize, 100> = KdTree2D::new(&points).unwrap();
+            assert_eq!(tree.len(), count);
+            let mut rng = LcgRng::new(seed + 100);
+            for _ in 0..50 {
+                let query = random_point(&mut rng);
+                let found = *tree.nearest(&query).unwrap();
+                let closest = points
+                    .iter()
+                    .map(|(point, _id)| distance_squared(point, &query))
+                    .fold(f32::INFINITY, f32::min);
+                assert_eq!(distance_squared(&points[found].0, &query), closest);
+            }
+        }
+    }
+
+    #[test]
+    pub fn within_radius_matches_brute_force() {
+        let points = random_points(9, 80);
+        let tree: KdTree2D<usize, 80> = KdTree2D::new(&points).unwrap();
+        let mut rng = LcgRng::new(10);
+        for radius in [0.0, 5.0, 20.0, 200.0] {
+            let query = random_point(&mut rng);
+            let mut found = BTreeSet::new();
+            tree.within_radius(&query, radius, |point, id| {
+                assert_eq!(*point, points[*id].0);
+                assert!(found.insert(*id), "Point {} was found twice", id);
+            });
+            let expected: BTreeSet<usize> = points
+                .iter()
+                .filter(|(point, _id)| distance_squared(point, &query) <= radius * radius)
+                .map(|(_point, id)| *id)
+                .collect();
+            assert_eq!(found, expected);
+        }
+        // A radius of 0 still finds a point at the query itself
+        let mut count = 0;
+        tree.within_radius(&points[3].0, 0.0, |_point, _id| count += 1);
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    pub fn duplicates_and_capacity() {
+        let same = NDimensionalPoint::new([1.0, 1.0]);
+        let points = [(same, 'a'), (same, 'b'), (same, 'c')];
+        let tree: KdTree2D<char, 3> = KdTree2D::new(&points).unwrap();
+        let mut found = Vec::new();
+        tree.within_radius(&same, 0.0, |_point, value| found.push(*value));
+        found.sort();
+        assert_eq!(found, ['a', 'b', 'c']);
+        assert!(KdTree2D::<char, 2>::new(&points).is_err());
+        let empty: KdTree2D<char, 2> = KdTree2D::new(&[]).unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(empty.nearest(&same), None);
+    }
+}
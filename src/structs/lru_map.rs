@@ -1,7 +1,10 @@
 /// The LruMap (i.e. Least Recently Used Map) is a map of keys to values, with a fixed capacity.
 /// Adding keys beyond the capacity will remove the least recently accessed key-value tuple and return it.
+///
+/// Entries can be pinned, so that they are never evicted.
 pub struct LruMap<K: PartialEq, V, const S: usize> {
-    data: arrayvec::ArrayVec<(usize, K, V), S>,
+    /// Tuples of last operation, pinned, key, and value
+    data: arrayvec::ArrayVec<(usize, bool, K, V), S>,
     next_operation: usize,
 }
 
@@ -15,18 +18,60 @@ impl<K: PartialEq, V, const S: usize> LruMap<K, V, S> {
     }
 
     /// Insert a new entry to the cache, and evict the least recently used one if capacity has been reached
+    ///
+    /// If the capacity has been reached and every entry is pinned (or the capacity is zero), the new
+    /// entry is not inserted, and is returned as if it was evicted straight away. Use try_insert to
+    /// tell that case apart from an eviction.
     pub fn insert(&mut self, key: K, value: V) -> Option<(K, V)> {
+        match self.try_insert(key, value) {
+            Ok(popped) => popped,
+            Err(rejected) => Some(rejected),
+        }
+    }
+
+    /// Insert a new entry to the cache, and evict the least recently used unpinned one if capacity has been reached
+    ///
+    /// If the capacity has been reached and there is nothing to evict, the entry is returned as an error.
+    pub fn try_insert(&mut self, key: K, value: V) -> Result<Option<(K, V)>, (K, V)> {
         let mut popped = None;
-        let new_op = self.get_and_inc_op();
         if self.data.is_full() {
-            let lru = self.least_recently_used().expect(
-                "Capacity was full and LRU was not found. Confirm LRU Map capacity is not zero?",
-            );
-            let (_op, k, v) = self.data.remove(lru);
+            let lru = match self.least_recently_used() {
+                None => return Err((key, value)),
+                Some(lru) => lru,
+            };
+            let (_op, _pinned, k, v) = self.data.remove(lru);
             popped = Some((k, v));
         }
-        self.data.push((new_op, key, value));
-        popped
+        let new_op = self.get_and_inc_op();
+        self.data.push((new_op, false, key, value));
+        Ok(popped)
+    }
+
+    /// Insert a new entry like try_insert, and pass the evicted entry to a callback, such as to
+    /// write it back to storage
+    ///
+    /// If the capacity has been reached and there is nothing to evict, the entry is returned as an
+    /// error, and the callback is not called.
+    pub fn insert_with_evict<F: FnMut(K, V)>(
+        &mut self,
+        key: K,
+        value: V,
+        mut on_evict: F,
+    ) -> Result<(), (K, V)> {
+        if let Some((k, v)) = self.try_insert(key, value)? {
+            on_evict(k, v);
+        }
+        Ok(())
+    }
+
+    /// Pin an entry, so that it is never evicted. Returns false if the key was not found.
+    pub fn pin(&mut self, key: &K) -> bool {
+        self.set_pinned(key, true)
+    }
+
+    /// Unpin an entry, so that it can be evicted again. Returns false if the key was not found.
+    pub fn unpin(&mut self, key: &K) -> bool {
+        self.set_pinned(key, false)
     }
 
     /// Get the value by key if it exists
@@ -34,7 +79,7 @@ impl<K: PartialEq, V, const S: usize> LruMap<K, V, S> {
     /// If you need a mutable reference, you can use "as_mut"
     pub fn get(&mut self, key: &K) -> Option<&V> {
         let new_op = self.get_and_inc_op();
-        for (op, k, v) in self.data.iter_mut() {
+        for (op, _pinned, k, v) in self.data.iter_mut() {
             if key == k {
                 *op = new_op;
                 return Some(v);
@@ -43,7 +88,46 @@ impl<K: PartialEq, V, const S: usize> LruMap<K, V, S> {
         None
     }
 
-    /// Returns None if there is still more capacity, or if there is no LRU.
+    /// Remove all the entries for which the predicate returns false, including pinned entries
+    ///
+    /// The remaining entries keep their recency.
+    pub fn retain<F: FnMut(&K, &mut V) -> bool>(&mut self, f: F) {
+        self.retain_with_evict(f, |_k, _v| {});
+    }
+
+    /// Remove all the entries for which the predicate returns false, like retain, and pass each
+    /// removed entry to a callback
+    pub fn retain_with_evict<F, E>(&mut self, mut f: F, mut on_evict: E)
+    where
+        F: FnMut(&K, &mut V) -> bool,
+        E: FnMut(K, V),
+    {
+        let mut index = 0;
+        while index < self.data.len() {
+            let (_op, _pinned, k, v) = &mut self.data[index];
+            if f(k, v) {
+                index += 1;
+            } else {
+                // The order of the entries does not matter, as the recency is kept in each entry
+                let (_op, _pinned, k, v) = self.data.swap_remove(index);
+                on_evict(k, v);
+            }
+        }
+    }
+
+    /// Remove all the entries, including pinned entries
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+
+    /// Remove all the entries, like clear, and pass each removed entry to a callback
+    pub fn clear_with_evict<F: FnMut(K, V)>(&mut self, mut on_evict: F) {
+        for (_op, _pinned, k, v) in self.data.drain(..) {
+            on_evict(k, v);
+        }
+    }
+
+    /// Returns None if there is still more capacity, or if there is no unpinned LRU.
     pub fn get_least_recently_used(&mut self) -> Option<(&mut K, &mut V)> {
         if !self.data.is_full() {
             return None;
@@ -52,7 +136,7 @@ impl<K: PartialEq, V, const S: usize> LruMap<K, V, S> {
             None => None,
             Some(index) => {
                 let new_op = self.get_and_inc_op();
-                let (op, k, v) = self.data.get_mut(index)?;
+                let (op, _pinned, k, v) = self.data.get_mut(index)?;
                 *op = new_op;
                 Some((k, v))
             }
@@ -69,19 +153,37 @@ impl<K: PartialEq, V, const S: usize> LruMap<K, V, S> {
         self.data.len()
     }
 
+    /// Returns true if the map contains no entries
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
     fn get_and_inc_op(&mut self) -> usize {
         let v = self.next_operation;
         self.next_operation += 1;
         v
     }
 
+    fn set_pinned(&mut self, key: &K, pinned: bool) -> bool {
+        for (_op, p, k, _v) in self.data.iter_mut() {
+            if key == k {
+                *p = pinned;
+                return true;
+            }
+        }
+        false
+    }
+
     fn least_recently_used(&self) -> Option<usize> {
         struct IndexAndOp {
             index: usize,
             operation: usize,
         }
         let mut ret_least_index: Option<IndexAndOp> = None;
-        for (i, (sz, _k, _v)) in self.data.iter().enumerate() {
+        for (i, (sz, pinned, _k, _v)) in self.data.iter().enumerate() {
+            if *pinned {
+                continue;
+            }
             match &mut ret_least_index {
                 None => {
                     ret_least_index = Some(IndexAndOp {
@@ -103,9 +205,16 @@ impl<K: PartialEq, V, const S: usize> LruMap<K, V, S> {
     }
 }
 
+impl<K: PartialEq, V, const S: usize> Default for LruMap<K, V, S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::structs::lru_map::LruMap;
+    use std::vec::Vec;
 
     #[test]
     pub fn can_add_and_remove_lru() {
@@ -119,4 +228,79 @@ mod test {
         let evicted = lru.insert(4, "four").unwrap();
         assert_eq!(evicted, (3, "three"));
     }
+
+    #[test]
+    pub fn pinned_entries_are_not_evicted() {
+        let mut lru: LruMap<_, _, 2> = LruMap::new();
+        assert!(lru.insert(1, "one").is_none());
+        assert!(lru.insert(2, "two").is_none());
+        assert!(lru.pin(&1));
+        assert!(!lru.pin(&5));
+        assert_eq!(lru.insert(3, "three"), Some((2, "two")));
+        assert_eq!(lru.insert(4, "four"), Some((3, "three")));
+        assert_eq!(lru.get(&1), Some(&"one"));
+
+        // Nothing can be evicted once everything is pinned
+        assert!(lru.pin(&4));
+        assert_eq!(lru.try_insert(5, "five"), Err((5, "five")));
+        assert!(lru.get_least_recently_used().is_none());
+        // Insert does not panic, and hands the new entry back instead
+        assert_eq!(lru.insert(5, "five"), Some((5, "five")));
+        assert_eq!(lru.len(), 2);
+        assert_eq!(lru.get(&5), None);
+        let mut empty: LruMap<_, _, 0> = LruMap::new();
+        assert_eq!(empty.insert(1, "one"), Some((1, "one")));
+
+        assert!(lru.unpin(&1));
+        assert_eq!(lru.try_insert(5, "five"), Ok(Some((1, "one"))));
+    }
+
+    #[test]
+    pub fn retain_keeps_matching_entries() {
+        let mut lru: LruMap<_, _, 4> = LruMap::new();
+        for i in 1..=4 {
+            lru.insert(i, i * 10);
+        }
+        // Make 2 more recent than 4
+        assert_eq!(lru.get(&2), Some(&20));
+        lru.retain(|k, v| {
+            *v += 1;
+            k % 2 == 0
+        });
+        assert_eq!(lru.len(), 2);
+        assert_eq!(lru.get(&1), None);
+        assert_eq!(lru.get(&3), None);
+        assert!(lru.insert(5, 50).is_none());
+        assert!(lru.insert(6, 60).is_none());
+        assert_eq!(lru.insert(7, 70), Some((4, 41)));
+        assert_eq!(lru.insert(8, 80), Some((2, 21)));
+    }
+
+    #[test]
+    pub fn evict_callbacks_see_every_eviction() {
+        let mut evicted = Vec::new();
+        let mut lru: LruMap<_, _, 3> = LruMap::new();
+        for i in 1..=5 {
+            assert!(lru
+                .insert_with_evict(i, i * 10, |k, v| evicted.push((k, v)))
+                .is_ok());
+        }
+        assert_eq!(evicted, [(1, 10), (2, 20)]);
+        // Nothing is evicted when every entry is pinned
+        for key in [3, 4, 5] {
+            assert!(lru.pin(&key));
+        }
+        assert_eq!(
+            lru.insert_with_evict(6, 60, |k, v| evicted.push((k, v))),
+            Err((6, 60))
+        );
+        assert_eq!(evicted, [(1, 10), (2, 20)]);
+        // Retain and clear remove pinned entries too
+        lru.retain_with_evict(|k, _v| *k != 4, |k, v| evicted.push((k, v)));
+        assert_eq!(evicted, [(1, 10), (2, 20), (4, 40)]);
+        lru.clear_with_evict(|k, v| evicted.push((k, v)));
+        assert!(lru.is_empty());
+        evicted.sort();
+        assert_eq!(evicted, [(1, 10), (2, 20), (3, 30), (4, 40), (5, 50)]);
+    }
 }
@@ -8,11 +8,26 @@
 //! and dimensions they need, and conveniently apply them to the algorithms, without having to write
 //! Into traits for tuples or arrays.
 
+pub mod dsp;
+mod fixed_vec;
+pub mod game;
+mod indexed_heap;
+mod kdtree;
 mod lru_map;
+mod stats;
+mod timer_wheel;
 
+pub use fixed_vec::FixedVec;
+pub use indexed_heap::IndexedMinHeap;
+pub use kdtree::KdTree2D;
 pub use lru_map::LruMap;
+pub use stats::SlidingWindowMax;
+pub use timer_wheel::TimerWheel;
 
-use core::ops::Add;
+use crate::algos::math::sqrt;
+use crate::algos::slice::select_nth_by;
+use core::cmp::Ordering;
+use core::ops::{Add, Sub};
 
 /// A n-dimensional point that is used in the spatial data structures
 ///
@@ -54,6 +69,15 @@ where
         NDimensionalPoint { dimensions: vals }
     }
 
+    /// Create a new n-dimensional spatial point from a slice of runtime length
+    ///
+    /// Returns an error if the length of the slice is not the number of dimensions.
+    #[allow(clippy::result_unit_err)]
+    pub fn from_slice(vals: &[Unit]) -> Result<Self, ()> {
+        let dimensions = vals.try_into().map_err(|_| ())?;
+        Ok(NDimensionalPoint { dimensions })
+    }
+
     /// Mutably set the dimension to a value for the point
     pub fn mut_set(&mut self, dimension: usize, value: Unit) {
         self.dimensions[dimension] = value;
@@ -75,6 +99,61 @@ where
     pub fn dimension_mut(&mut self, dimension: usize) -> &mut Unit {
         &mut self.dimensions[dimension]
     }
+
+    /// Add two points together, dimension by dimension
+    ///
+    /// Each addition is checked in **SumType**, and the sum is then converted back into **Unit**.
+    /// If any of the sums overflows **SumType** or does not fit in **Unit**, None is returned.
+    pub fn checked_add(&self, other: &Self) -> Option<NDimensionalPoint<Unit, SumType, S>>
+    where
+        Unit: CheckedSum<SumType> + TryFromSum<SumType>,
+    {
+        let mut dimensions = self.dimensions;
+        for (i, dimension) in dimensions.iter_mut().enumerate() {
+            let sum = self.dimensions[i].checked_sum(other.dimensions[i])?;
+            *dimension = Unit::try_from_sum(sum)?;
+        }
+        Some(NDimensionalPoint { dimensions })
+    }
+
+    /// Find the point with the median value along one axis, such as to split the points when
+    /// building a k-d tree
+    ///
+    /// The points are reordered with quickselect, so that the points before the median are not
+    /// greater along the axis, and the points after it are not less. For an even number of points
+    /// the lower of the two middle points is returned. Returns None if there are no points.
+    ///
+    /// Panics if the axis is not below the number of dimensions **S**.
+    pub fn median_by_axis(points: &mut [Self], axis: usize) -> Option<Self> {
+        assert!(axis < S, "Axis {axis} must be below the dimensions {S}");
+        if points.is_empty() {
+            return None;
+        }
+        let middle = (points.len() - 1) / 2;
+        select_nth_by(points, middle, |a, b| {
+            a.dimensions[axis]
+                .partial_cmp(&b.dimensions[axis])
+                .unwrap_or(Ordering::Equal)
+        });
+        Some(points[middle])
+    }
+}
+
+/// Converts a value of the larger **SumType** back into the **Unit** it was calculated from
+///
+/// This is implemented for every type that implements `TryFrom<SumType>`.
+pub trait TryFromSum<SumType>: Sized {
+    /// Returns None if the sum does not fit in the unit
+    fn try_from_sum(sum: SumType) -> Option<Self>;
+}
+
+impl<Unit, SumType> TryFromSum<SumType> for Unit
+where
+    Unit: TryFrom<SumType>,
+{
+    fn try_from_sum(sum: SumType) -> Option<Self> {
+        Unit::try_from(sum).ok()
+    }
 }
 
 // Copy is manually implemented because derive copy doesnt work for slices
@@ -89,6 +168,9 @@ where
 ///
 /// Examples of such shapes include rectangles for 2D, and cubes for 3D. There is the added
 /// constraint that they can not be skewed, but must be perfectly aligned with axis.
+///
+/// For floats, the **Unit** and **SumType** can be the same type, such as
+/// `AxisAlignedBoundingBox<f32, f32, 2>`, since every type converts into itself.
 #[derive(PartialEq, Copy, Clone)]
 #[cfg_attr(test, derive(Debug))]
 pub struct AxisAlignedBoundingBox<Unit, SumType, const S: usize>
@@ -112,12 +194,12 @@ where
 
     /// Returns a reference to the point of origin of this bound
     pub fn origin(&self) -> &NDimensionalPoint<Unit, SumType, S> {
-        return &self.origin;
+        &self.origin
     }
 
     /// Returns a mutable reference to the point of origin of this bound
     pub fn mut_origin(&mut self) -> &mut NDimensionalPoint<Unit, SumType, S> {
-        return &mut self.origin;
+        &mut self.origin
     }
 
     /// Checks if this AABB intersects with another AABB exclusive of edges.
@@ -151,13 +233,273 @@ where
         }
         true
     }
+
+    /// Same as intersects_exc, but returns None if calculating the far edge of a box overflows
+    /// **SumType**, rather than wrapping or panicking.
+    pub fn intersects_exc_checked(
+        &self,
+        other: &AxisAlignedBoundingBox<Unit, SumType, S>,
+    ) -> Option<bool>
+    where
+        Unit: CheckedSum<SumType>,
+    {
+        for i in 0..S {
+            let self_min = *self.origin.dimension(i);
+            let self_max = self_min.checked_sum(self.widths[i])?;
+
+            let other_min = *other.origin.dimension(i);
+            let other_max = other_min.checked_sum(other.widths[i])?;
+
+            if self_max <= other_min.into() || other_max <= self_min.into() {
+                return Some(false);
+            }
+        }
+        Some(true)
+    }
+
+    /// Same as intersects_inc, but returns None if calculating the far edge of a box overflows
+    /// **SumType**, rather than wrapping or panicking.
+    pub fn intersects_inc_checked(
+        &self,
+        other: &AxisAlignedBoundingBox<Unit, SumType, S>,
+    ) -> Option<bool>
+    where
+        Unit: CheckedSum<SumType>,
+    {
+        for i in 0..S {
+            let self_min = *self.origin.dimension(i);
+            let self_max = self_min.checked_sum(self.widths[i])?;
+
+            let other_min = *other.origin.dimension(i);
+            let other_max = other_min.checked_sum(other.widths[i])?;
+
+            if self_max < other_min.into() || other_max < self_min.into() {
+                return Some(false);
+            }
+        }
+        Some(true)
+    }
+
+    /// Returns how far this AABB overlaps with another AABB in each dimension, or None if they do
+    /// not intersect (exclusive of edges).
+    ///
+    /// The overlap is the smallest distance that one of the boxes would need to move along that
+    /// dimension to separate them, which is useful for collision response. When one box contains
+    /// the other along a dimension, this is the distance to the nearer edge, not the width of the
+    /// inner box.
+    ///
+    /// The distances are calculated in **Unit**, not **SumType**, so the distance between the two
+    /// boxes along each dimension must fit in **Unit**. For example, with an `i8` unit, boxes at
+    /// -100 and 100 overflow.
+    pub fn overlap_widths(
+        &self,
+        other: &AxisAlignedBoundingBox<Unit, SumType, S>,
+    ) -> Option<[Unit; S]>
+    where
+        Unit: Sub<Output = Unit>,
+    {
+        let mut overlap = self.widths;
+        for (i, overlap) in overlap.iter_mut().enumerate() {
+            let self_extent = Self::positive_extent(*self.origin.dimension(i), self.widths[i]);
+            let other_extent = Self::positive_extent(*other.origin.dimension(i), other.widths[i]);
+            // Order the extents, so that the first one starts before the second one
+            let ((first_min, first_width), (second_min, second_width)) =
+                if self_extent.0 <= other_extent.0 {
+                    (self_extent, other_extent)
+                } else {
+                    (other_extent, self_extent)
+                };
+            let gap = second_min - first_min;
+            if first_width <= gap {
+                return None;
+            }
+            // The boxes separate by moving the second box past the end of the first one, or past
+            // the start of it, which is only nearer if the second box ends before the first one
+            let past_end = first_width - gap;
+            *overlap = if past_end > gap && second_width < past_end - gap {
+                // This is gap + second_width, but the sum of two units is a SumType
+                let nearer_by = past_end - gap - second_width;
+                past_end - nearer_by
+            } else {
+                past_end
+            };
+        }
+        Some(overlap)
+    }
+
+    /// Returns the minimum and width of an extent, so that the width is not negative
+    fn positive_extent(origin: Unit, width: Unit) -> (Unit, Unit)
+    where
+        Unit: Sub<Output = Unit>,
+    {
+        // Subtracting the width from itself is the only zero available for a generic unit
+        #[allow(clippy::eq_op)]
+        let zero = width - width;
+        if width < zero {
+            (origin - (zero - width), zero - width)
+        } else {
+            (origin, width)
+        }
+    }
+}
+
+/// Adds two units into a **SumType**, returning None if the sum does not fit
+///
+/// This is implemented for the primitive numbers, where the unit and the sum are the same type.
+/// Floats never overflow, as they become infinite instead.
+pub trait CheckedSum<SumType> {
+    /// Returns the sum, or None on overflow
+    fn checked_sum(self, rhs: Self) -> Option<SumType>;
+}
+
+macro_rules! impl_checked_sum_int {
+    ($($t:ty),*) => {
+        $(
+            impl CheckedSum<$t> for $t {
+                fn checked_sum(self, rhs: Self) -> Option<$t> {
+                    self.checked_add(rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_checked_sum_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl CheckedSum<f32> for f32 {
+    fn checked_sum(self, rhs: Self) -> Option<f32> {
+        Some(self + rhs)
+    }
+}
+
+impl CheckedSum<f64> for f64 {
+    fn checked_sum(self, rhs: Self) -> Option<f64> {
+        Some(self + rhs)
+    }
+}
+
+/// A way of measuring the distance between two points, so that spatial data structures can be
+/// used with whichever metric suits the data
+pub trait Metric<T, const DIM: usize> {
+    /// Returns the distance between two points
+    fn distance(a: &[T; DIM], b: &[T; DIM]) -> T;
+}
+
+/// The straight line distance between two points
+pub struct Euclidean;
+
+/// The sum of the distances along each axis, such as when moving along a grid
+pub struct Manhattan;
+
+/// The largest of the distances along each axis, such as a king moving on a chess board
+pub struct Chebyshev;
+
+impl<const DIM: usize> Metric<f32, DIM> for Euclidean {
+    fn distance(a: &[f32; DIM], b: &[f32; DIM]) -> f32 {
+        let mut sum = 0.0;
+        for i in 0..DIM {
+            let difference = a[i] - b[i];
+            sum += difference * difference;
+        }
+        sqrt(sum)
+    }
+}
+
+impl<T, const DIM: usize> Metric<T, DIM> for Manhattan
+where
+    T: Copy + Default + PartialOrd + Add<Output = T> + Sub<Output = T>,
+{
+    fn distance(a: &[T; DIM], b: &[T; DIM]) -> T {
+        let mut sum = T::default();
+        for i in 0..DIM {
+            sum = sum + abs_difference(a[i], b[i]);
+        }
+        sum
+    }
+}
+
+impl<T, const DIM: usize> Metric<T, DIM> for Chebyshev
+where
+    T: Copy + Default + PartialOrd + Sub<Output = T>,
+{
+    fn distance(a: &[T; DIM], b: &[T; DIM]) -> T {
+        let mut max = T::default();
+        for i in 0..DIM {
+            let difference = abs_difference(a[i], b[i]);
+            if difference > max {
+                max = difference;
+            }
+        }
+        max
+    }
+}
+
+/// A hash function over bytes, so that hashed data structures can be used without
+/// `core::hash::Hasher` and with whichever function suits the data
+///
+/// The hash of a value must only depend on its bytes, so it is the same on every run and device.
+pub trait NoStdHash {
+    /// Hash a buffer of bytes
+    fn hash_bytes(bytes: &[u8]) -> u64;
+}
+
+/// The 64-bit FNV-1a hash, which mixes one byte at a time and spreads short keys well
+pub struct Fnv1a;
+
+/// A hash in the style of FxHash, which mixes eight bytes at a time, so it is faster for long
+/// keys but spreads similar keys less well than FNV-1a
+pub struct Fxhash;
+
+impl NoStdHash for Fnv1a {
+    fn hash_bytes(bytes: &[u8]) -> u64 {
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        for byte in bytes {
+            hash ^= *byte as u64;
+            hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+        hash
+    }
+}
+
+impl NoStdHash for Fxhash {
+    fn hash_bytes(bytes: &[u8]) -> u64 {
+        let mut hash: u64 = 0;
+        let mut add = |word: u64| {
+            hash = (hash.rotate_left(5) ^ word).wrapping_mul(0x517c_c1b7_2722_0a95);
+        };
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            let mut word = [0; 8];
+            word.copy_from_slice(chunk);
+            add(u64::from_le_bytes(word));
+        }
+        for byte in chunks.remainder() {
+            add(*byte as u64);
+        }
+        // Mix in the length, so that trailing zero bytes change the hash
+        add(bytes.len() as u64);
+        hash
+    }
+}
+
+fn abs_difference<T: PartialOrd + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
 }
 
 #[cfg(test)]
 mod test {
+    use arrayvec::ArrayVec;
     use core::ops::Add;
 
-    use crate::structs::{AxisAlignedBoundingBox, NDimensionalPoint};
+    use crate::structs::{
+        AxisAlignedBoundingBox, Chebyshev, CheckedSum, Euclidean, Fnv1a, Fxhash, Manhattan, Metric,
+        NDimensionalPoint, NoStdHash,
+    };
+    use std::collections::BTreeSet;
 
     #[derive(PartialEq, Copy, Clone, PartialOrd)]
     struct SomeStruct {
@@ -176,6 +518,63 @@ mod test {
         }
     }
 
+    /// A unit that sums into a wider type, like the SumType is intended for
+    #[derive(PartialEq, Copy, Clone, PartialOrd, Debug)]
+    struct Byte(i8);
+
+    impl Add for Byte {
+        type Output = i16;
+
+        fn add(self, rhs: Self) -> Self::Output {
+            self.0 as i16 + rhs.0 as i16
+        }
+    }
+
+    impl CheckedSum<i16> for Byte {
+        fn checked_sum(self, rhs: Self) -> Option<i16> {
+            Some(self + rhs)
+        }
+    }
+
+    impl TryFrom<i16> for Byte {
+        type Error = ();
+
+        fn try_from(value: i16) -> Result<Self, Self::Error> {
+            i8::try_from(value).map(Byte).map_err(|_| ())
+        }
+    }
+
+    #[test]
+    fn checked_add_within_unit() {
+        let left: NDimensionalPoint<Byte, i16, 2> = NDimensionalPoint::new([Byte(100), Byte(-100)]);
+        let right = NDimensionalPoint::new([Byte(27), Byte(-28)]);
+        let sum = left.checked_add(&right).unwrap();
+        assert_eq!(*sum.dimension(0), Byte(127));
+        assert_eq!(*sum.dimension(1), Byte(-128));
+    }
+
+    #[test]
+    fn checked_add_overflows_unit() {
+        let left: NDimensionalPoint<Byte, i16, 2> = NDimensionalPoint::new([Byte(1), Byte(120)]);
+        let right = NDimensionalPoint::new([Byte(1), Byte(8)]);
+        assert_eq!(left.checked_add(&right), None);
+        let negative = NDimensionalPoint::new([Byte(-128), Byte(0)]);
+        assert_eq!(
+            negative.checked_add(&NDimensionalPoint::new([Byte(-1), Byte(0)])),
+            None
+        );
+    }
+
+    #[test]
+    fn checked_add_overflows_sum() {
+        let max: NDimensionalPoint<i32, i32, 2> = NDimensionalPoint::new([i32::MAX, 0]);
+        assert_eq!(max.checked_add(&NDimensionalPoint::new([1, 0])), None);
+        assert_eq!(
+            max.checked_add(&NDimensionalPoint::new([-1, i32::MIN])),
+            Some(NDimensionalPoint::new([i32::MAX - 1, i32::MIN]))
+        );
+    }
+
     #[test]
     fn can_compare_ndimensional_point() {
         let mut left: NDimensionalPoint<i32, i32, 2> = NDimensionalPoint::new([1, 2]);
@@ -191,6 +590,15 @@ mod test {
         assert_eq!(left, right);
     }
 
+    #[test]
+    fn can_create_point_from_slice() {
+        let values = [1, 2, 3, 4];
+        let point = NDimensionalPoint::<i32, i32, 3>::from_slice(&values[..3]).unwrap();
+        assert_eq!(point, NDimensionalPoint::new([1, 2, 3]));
+        assert!(NDimensionalPoint::<i32, i32, 3>::from_slice(&values[..2]).is_err());
+        assert!(NDimensionalPoint::<i32, i32, 3>::from_slice(&values).is_err());
+    }
+
     #[test]
     fn can_compare_aabb() {
         let point1: NDimensionalPoint<i32, i32, 3> = NDimensionalPoint::new([1, 2, 3]);
@@ -245,4 +653,167 @@ mod test {
         assert!(!left_medium.intersects_exc(&small));
         assert!(!left_small.intersects_exc(&small));
     }
+
+    #[test]
+    fn check_overlap_widths() {
+        let big = AxisAlignedBoundingBox::new(NDimensionalPoint::new([0, 0]), [10, 10]);
+        let shifted = AxisAlignedBoundingBox::new(NDimensionalPoint::new([5, 5]), [10, 10]);
+        let thin = AxisAlignedBoundingBox::new(NDimensionalPoint::new([8, 2]), [1, 20]);
+        let medium = AxisAlignedBoundingBox::new(NDimensionalPoint::new([5, 5]), [-5, -5]);
+
+        assert_eq!(big.overlap_widths(&shifted), Some([5, 5]));
+        assert_eq!(shifted.overlap_widths(&big), Some([5, 5]));
+        // The thin box is inside the others along x, and nearer one edge than the other
+        assert_eq!(big.overlap_widths(&thin), Some([2, 8]));
+        assert_eq!(thin.overlap_widths(&big), Some([2, 8]));
+        assert_eq!(shifted.overlap_widths(&thin), Some([4, 13]));
+        assert_eq!(big.overlap_widths(&medium), Some([5, 5]));
+    }
+
+    #[test]
+    fn check_overlap_widths_disjoint() {
+        let left = AxisAlignedBoundingBox::new(NDimensionalPoint::new([0, 0]), [5, 5]);
+        let touching = AxisAlignedBoundingBox::new(NDimensionalPoint::new([5, 0]), [5, 5]);
+        let far = AxisAlignedBoundingBox::new(NDimensionalPoint::new([0, 20]), [5, 5]);
+        let small = AxisAlignedBoundingBox::new(NDimensionalPoint::new([10, 10]), [-1, -1]);
+
+        assert_eq!(left.overlap_widths(&touching), None);
+        assert_eq!(left.overlap_widths(&far), None);
+        assert_eq!(far.overlap_widths(&left), None);
+        assert_eq!(left.overlap_widths(&small), None);
+    }
+
+    #[test]
+    fn check_float_aabb_2d() {
+        let big: AxisAlignedBoundingBox<f32, f32, 2> =
+            AxisAlignedBoundingBox::new(NDimensionalPoint::new([0.0, 0.0]), [10.0, 10.0]);
+        let touching = AxisAlignedBoundingBox::new(NDimensionalPoint::new([10.0, 2.5]), [1.0, 1.0]);
+        let inside = AxisAlignedBoundingBox::new(NDimensionalPoint::new([9.5, 9.5]), [-1.5, -1.5]);
+        let far = AxisAlignedBoundingBox::new(NDimensionalPoint::new([10.5, 0.0]), [1.0, 1.0]);
+
+        assert!(big.intersects_inc(&touching));
+        assert!(!big.intersects_exc(&touching));
+        assert!(big.intersects_exc(&inside));
+        assert!(!big.intersects_inc(&far));
+        assert_eq!(big.intersects_exc_checked(&inside), Some(true));
+        assert_eq!(big.overlap_widths(&inside), Some([2.0, 2.0]));
+        assert_eq!(big.overlap_widths(&touching), None);
+    }
+
+    #[test]
+    fn check_float_aabb_3d() {
+        let cube: AxisAlignedBoundingBox<f64, f64, 3> =
+            AxisAlignedBoundingBox::new(NDimensionalPoint::new([0.0, 0.0, 0.0]), [1.0, 1.0, 1.0]);
+        let corner = AxisAlignedBoundingBox::new(
+            NDimensionalPoint::new([0.75, 0.75, 0.75]),
+            [1.0, 1.0, 1.0],
+        );
+        let above =
+            AxisAlignedBoundingBox::new(NDimensionalPoint::new([0.5, 0.5, 1.25]), [1.0, 1.0, 1.0]);
+
+        assert!(cube.intersects_exc(&corner));
+        assert!(!cube.intersects_inc(&above));
+        assert_eq!(cube.overlap_widths(&corner), Some([0.25, 0.25, 0.25]));
+        assert_eq!(cube.intersects_inc_checked(&above), Some(false));
+    }
+
+    fn nearest<M: Metric<i32, 2>>(query: &[i32; 2], candidates: &[[i32; 2]]) -> [i32; 2] {
+        let mut nearest = candidates[0];
+        for candidate in candidates {
+            if M::distance(query, candidate) < M::distance(query, &nearest) {
+                nearest = *candidate;
+            }
+        }
+        nearest
+    }
+
+    #[test]
+    fn check_metrics() {
+        assert_eq!(Manhattan::distance(&[1, -2, 3], &[-1, 2, 3]), 6);
+        assert_eq!(Chebyshev::distance(&[1, -2, 3], &[-1, 2, 3]), 4);
+        assert_eq!(Euclidean::distance(&[0.0, 0.0], &[3.0, -4.0]), 5.0);
+        assert_eq!(Manhattan::distance(&[0.5f32], &[2.0]), 1.5);
+    }
+
+    #[test]
+    fn metrics_change_nearest() {
+        let candidates = [[3, 3], [5, 0]];
+        assert_eq!(nearest::<Manhattan>(&[0, 0], &candidates), [5, 0]);
+        assert_eq!(nearest::<Chebyshev>(&[0, 0], &candidates), [3, 3]);
+    }
+
+    #[test]
+    fn check_intersects_checked() {
+        let big = AxisAlignedBoundingBox::new(NDimensionalPoint::new([0, 0]), [10, 10]);
+        let left_medium = AxisAlignedBoundingBox::new(NDimensionalPoint::new([0, 0]), [5, 5]);
+        let touching = AxisAlignedBoundingBox::new(NDimensionalPoint::new([5, 0]), [5, 5]);
+        assert_eq!(big.intersects_exc_checked(&left_medium), Some(true));
+        assert_eq!(left_medium.intersects_exc_checked(&touching), Some(false));
+        assert_eq!(left_medium.intersects_inc_checked(&touching), Some(true));
+    }
+
+    #[test]
+    fn check_intersects_checked_overflow() {
+        let big = AxisAlignedBoundingBox::new(NDimensionalPoint::new([0, 0]), [10, 10]);
+        let huge: AxisAlignedBoundingBox<i32, i32, 2> =
+            AxisAlignedBoundingBox::new(NDimensionalPoint::new([5, i32::MAX - 5]), [5, 10]);
+        let negative: AxisAlignedBoundingBox<i32, i32, 2> =
+            AxisAlignedBoundingBox::new(NDimensionalPoint::new([i32::MIN, 0]), [-1, 1]);
+        assert_eq!(big.intersects_exc_checked(&huge), None);
+        assert_eq!(huge.intersects_inc_checked(&big), None);
+        assert_eq!(negative.intersects_inc_checked(&big), None);
+    }
+
+    #[test]
+    pub fn median_along_each_axis() {
+        let mut points: ArrayVec<NDimensionalPoint<i32, i32, 2>, 8> =
+            [[5, 0], [1, 9], [4, 3], [2, 7], [3, 1]]
+                .into_iter()
+                .map(NDimensionalPoint::new)
+                .collect();
+        let median = NDimensionalPoint::median_by_axis(&mut points, 0).unwrap();
+        assert_eq!(median, NDimensionalPoint::new([3, 1]));
+        for point in &points[..2] {
+            assert!(*point.dimension(0) <= 3);
+        }
+        let median = NDimensionalPoint::median_by_axis(&mut points, 1).unwrap();
+        assert_eq!(median, NDimensionalPoint::new([4, 3]));
+        // With an even number of points, the lower middle point is the median
+        let mut points =
+            [[0, 4], [0, 1], [0, 3], [0, 2]].map(NDimensionalPoint::<i32, i32, 2>::new);
+        let median = NDimensionalPoint::median_by_axis(&mut points, 1).unwrap();
+        assert_eq!(median, NDimensionalPoint::new([0, 2]));
+        let mut empty: [NDimensionalPoint<i32, i32, 2>; 0] = [];
+        assert!(NDimensionalPoint::median_by_axis(&mut empty, 0).is_none());
+    }
+
+    #[test]
+    fn fnv1a_known_outputs() {
+        assert_eq!(Fnv1a::hash_bytes(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(Fnv1a::hash_bytes(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(Fnv1a::hash_bytes(b"foobar"), 0x8594_4171_f739_67e8);
+    }
+
+    /// Hash a sample of distinct keys, and return how many hashes were already taken
+    fn count_collisions<H: NoStdHash>() -> usize {
+        let mut hashes = BTreeSet::new();
+        let mut keys = 0;
+        for i in 0u32..10000 {
+            // Small integers, and the same bytes with trailing zeros, are common keys
+            let bytes = i.to_le_bytes();
+            let padded = [bytes[0], bytes[1], 0, 0, 0, 0, 0, 0, 0];
+            for key in [&bytes[..], &bytes[..2], &padded[..]] {
+                hashes.insert(H::hash_bytes(key));
+                keys += 1;
+            }
+        }
+        keys - hashes.len()
+    }
+
+    #[test]
+    fn hashes_rarely_collide() {
+        assert_eq!(count_collisions::<Fnv1a>(), 0);
+        assert_eq!(count_collisions::<Fxhash>(), 0);
+        assert_ne!(Fxhash::hash_bytes(&[1]), Fxhash::hash_bytes(&[1, 0]));
+    }
 }
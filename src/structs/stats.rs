@@ -0,0 +1,151 @@
+/// The SlidingWindowMax tracks the maximum of the last **N** samples of a stream, such as the peak
+/// of a signal over a moving window.
+///
+/// Only the samples that could still become the maximum are kept, in a monotonic deque over a
+/// fixed buffer, so each push takes constant time on average and reading the maximum is immediate.
+///
+/// ```
+/// use nostd_structs::structs::SlidingWindowMax;
+/// let mut window: SlidingWindowMax<i32, 2> = SlidingWindowMax::new();
+/// window.push(5);
+/// window.push(1);
+/// assert_eq!(window.max(), Some(5));
+/// window.push(2);
+/// assert_eq!(window.max(), Some(2));
+/// ```
+pub struct SlidingWindowMax<T: PartialOrd + Copy, const N: usize> {
+    /// Ring buffer of the sample number and value, with values decreasing from front to back
+    deque: [Option<(u64, T)>; N],
+    front: usize,
+    len: usize,
+    pushed: u64,
+}
+
+impl<T: PartialOrd + Copy, const N: usize> SlidingWindowMax<T, N> {
+    /// Create a new, empty window
+    pub const fn new() -> Self {
+        assert!(
+            N > 0,
+            "A SlidingWindowMax needs a window of at least one sample"
+        );
+        SlidingWindowMax {
+            deque: [None; N],
+            front: 0,
+            len: 0,
+            pushed: 0,
+        }
+    }
+
+    /// Add a sample to the window, dropping the oldest sample once the window is full
+    pub fn push(&mut self, sample: T) {
+        let number = self.pushed;
+        self.pushed += 1;
+        if let Some((oldest, _)) = self.get(0) {
+            if oldest + N as u64 <= number {
+                self.deque[self.front] = None;
+                self.front = (self.front + 1) % N;
+                self.len -= 1;
+            }
+        }
+        // Older samples that are not larger than the new one can never be the maximum again
+        while let Some((_, back)) = self.get(self.len.wrapping_sub(1)) {
+            if back > sample {
+                break;
+            }
+            self.len -= 1;
+            self.deque[(self.front + self.len) % N] = None;
+        }
+        self.deque[(self.front + self.len) % N] = Some((number, sample));
+        self.len += 1;
+    }
+
+    /// Returns the largest sample in the window, or None if nothing has been pushed
+    pub fn max(&self) -> Option<T> {
+        self.get(0).map(|(_, value)| value)
+    }
+
+    /// Returns the number of samples in the window
+    pub fn len(&self) -> usize {
+        // Compare before converting, as the count can be larger than a usize on 32-bit targets
+        if self.pushed >= N as u64 {
+            N
+        } else {
+            self.pushed as usize
+        }
+    }
+
+    /// Returns true if nothing has been pushed
+    pub fn is_empty(&self) -> bool {
+        self.pushed == 0
+    }
+
+    /// Returns the entry at a position of the deque, counting from the front
+    fn get(&self, position: usize) -> Option<(u64, T)> {
+        if position >= self.len {
+            return None;
+        }
+        self.deque[(self.front + position) % N]
+    }
+}
+
+impl<T: PartialOrd + Copy, const N: usize> Default for SlidingWindowMax<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::structs::stats::SlidingWindowMax;
+    use std::vec::Vec;
+
+    #[test]
+    pub fn tracks_max_as_window_slides() {
+        let mut window: SlidingWindowMax<i32, 3> = SlidingWindowMax::new();
+        assert_eq!(window.max(), None);
+        let samples = [1, 3, 2, 5, 4, 1, 0, 0, 6, 2];
+        let maxima: Vec<i32> = samples
+            .iter()
+            .map(|sample| {
+                window.push(*sample);
+                window.max().unwrap()
+            })
+            .collect();
+        assert_eq!(maxima, [1, 3, 3, 5, 5, 5, 4, 1, 6, 6]);
+        assert_eq!(window.len(), 3);
+    }
+
+    #[test]
+    pub fn matches_brute_force() {
+        let mut window: SlidingWindowMax<u8, 4> = SlidingWindowMax::new();
+        let samples: Vec<u8> = (0..50u32).map(|i| ((i * 37 + 11) % 23) as u8).collect();
+        for (i, sample) in samples.iter().enumerate() {
+            window.push(*sample);
+            let start = (i + 1).saturating_sub(4);
+            assert_eq!(window.max(), samples[start..=i].iter().max().copied());
+        }
+    }
+
+    #[test]
+    pub fn len_after_many_samples() {
+        let mut window: SlidingWindowMax<u8, 3> = SlidingWindowMax::new();
+        assert_eq!(window.len(), 0);
+        window.push(1);
+        assert_eq!(window.len(), 1);
+        // The count of samples passes u32::MAX, which must not wrap the length on 32-bit targets
+        window.pushed = u32::MAX as u64 + 1;
+        window.push(2);
+        assert_eq!(window.len(), 3);
+        assert!(!window.is_empty());
+        assert_eq!(window.max(), Some(2));
+    }
+
+    #[test]
+    pub fn window_of_one() {
+        let mut window: SlidingWindowMax<f32, 1> = SlidingWindowMax::new();
+        for sample in [2.0, -1.0, 0.5] {
+            window.push(sample);
+            assert_eq!(window.max(), Some(sample));
+        }
+    }
+}
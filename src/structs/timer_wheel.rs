@@ -0,0 +1,217 @@
+use arrayvec::ArrayVec;
+
+/// A TimerWheel schedules values to be released after a number of ticks, such as timed events in
+/// a game loop.
+///
+/// The wheel has **SLOTS** slots, one for each tick of a rotation, and each slot holds a list of
+/// the events that are due on that tick. Advancing jumps from one deadline to the next, and only
+/// looks at the slots of those ticks, so its cost depends on the events that fire rather than on
+/// the number of ticks. Events that are further away than one rotation stay in their slot until the wheel
+/// reaches the rotation they are due in.
+///
+/// Up to **CAPACITY** events can be scheduled at once. Ticks saturate at `u64::MAX`, where the
+/// wheel stops.
+pub struct TimerWheel<V, const SLOTS: usize, const CAPACITY: usize> {
+    events: [Option<Event<V>>; CAPACITY],
+    heads: [Option<usize>; SLOTS],
+    tails: [Option<usize>; SLOTS],
+    now: u64,
+    len: usize,
+}
+
+struct Event<V> {
+    deadline: u64,
+    value: V,
+    next: Option<usize>,
+}
+
+impl<V, const SLOTS: usize, const CAPACITY: usize> TimerWheel<V, SLOTS, CAPACITY> {
+    /// Create a new TimerWheel, starting at tick 0
+    pub const fn new() -> Self {
+        assert!(SLOTS > 0, "A TimerWheel needs at least one slot");
+        TimerWheel {
+            events: [const { None }; CAPACITY],
+            heads: [None; SLOTS],
+            tails: [None; SLOTS],
+            now: 0,
+            len: 0,
+        }
+    }
+
+    /// Schedule a value to be released after a number of ticks
+    ///
+    /// A delay of 0 is released on the next tick. Events due on the same tick are released in the
+    /// order they were scheduled. If the wheel is at capacity, the value is returned as an error.
+    pub fn schedule(&mut self, ticks_from_now: u64, value: V) -> Result<(), V> {
+        let index = match self.events.iter().position(|event| event.is_none()) {
+            None => return Err(value),
+            Some(index) => index,
+        };
+        let deadline = self.now.saturating_add(ticks_from_now.max(1));
+        let slot = (deadline % SLOTS as u64) as usize;
+        self.events[index] = Some(Event {
+            deadline,
+            value,
+            next: None,
+        });
+        match self.tails[slot] {
+            None => self.heads[slot] = Some(index),
+            Some(tail) => {
+                if let Some(tail) = &mut self.events[tail] {
+                    tail.next = Some(index);
+                }
+            }
+        }
+        self.tails[slot] = Some(index);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Move the wheel forward by a number of ticks, and return the events that became due, in the
+    /// order they were due.
+    pub fn advance(&mut self, ticks: u64) -> impl Iterator<Item = V> {
+        let mut fired = ArrayVec::<V, CAPACITY>::new();
+        let target = self.now.saturating_add(ticks);
+        while self.now < target {
+            match self.next_deadline() {
+                Some(deadline) if deadline <= target => {
+                    // No events are due on the ticks in between, so skip straight to the deadline
+                    self.now = deadline;
+                    self.fire_slot(&mut fired);
+                }
+                _ => self.now = target,
+            }
+        }
+        fired.into_iter()
+    }
+
+    /// Returns the number of ticks the wheel has advanced in total
+    pub fn now(&self) -> u64 {
+        self.now
+    }
+
+    /// Returns the number of events that are scheduled
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if there are no events scheduled
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the earliest deadline of the scheduled events
+    fn next_deadline(&self) -> Option<u64> {
+        self.events
+            .iter()
+            .flatten()
+            .map(|event| event.deadline)
+            .min()
+    }
+
+    /// Remove the events of the current slot that are due now, keeping those in later rotations
+    fn fire_slot(&mut self, fired: &mut ArrayVec<V, CAPACITY>) {
+        let slot = (self.now % SLOTS as u64) as usize;
+        let mut previous: Option<usize> = None;
+        let mut current = self.heads[slot];
+        while let Some(index) = current {
+            let (deadline, next) = match &self.events[index] {
+                Some(event) => (event.deadline, event.next),
+                None => unreachable!("Slots only link to scheduled events"),
+            };
+            if deadline > self.now {
+                previous = Some(index);
+                current = next;
+                continue;
+            }
+            // Unlink the event from the slot
+            match previous {
+                None => self.heads[slot] = next,
+                Some(previous) => {
+                    if let Some(previous) = &mut self.events[previous] {
+                        previous.next = next;
+                    }
+                }
+            }
+            if self.tails[slot] == Some(index) {
+                self.tails[slot] = previous;
+            }
+            if let Some(event) = self.events[index].take() {
+                fired.push(event.value);
+            }
+            self.len -= 1;
+            current = next;
+        }
+    }
+}
+
+impl<V, const SLOTS: usize, const CAPACITY: usize> Default for TimerWheel<V, SLOTS, CAPACITY> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::structs::timer_wheel::TimerWheel;
+    use std::vec::Vec;
+
+    #[test]
+    pub fn fires_on_the_correct_tick() {
+        let mut wheel: TimerWheel<&str, 8, 8> = TimerWheel::new();
+        wheel.schedule(1, "one").unwrap();
+        wheel.schedule(3, "three").unwrap();
+        wheel.schedule(3, "also three").unwrap();
+        wheel.schedule(0, "next").unwrap();
+        assert_eq!(wheel.advance(1).collect::<Vec<_>>(), ["one", "next"]);
+        assert_eq!(wheel.advance(1).count(), 0);
+        assert_eq!(
+            wheel.advance(1).collect::<Vec<_>>(),
+            ["three", "also three"]
+        );
+        assert!(wheel.is_empty());
+        assert_eq!(wheel.now(), 3);
+    }
+
+    #[test]
+    pub fn fires_beyond_one_rotation() {
+        let mut wheel: TimerWheel<u64, 4, 8> = TimerWheel::new();
+        // All of these share a slot with 2
+        for delay in [10, 2, 6, 14] {
+            wheel.schedule(delay, delay).unwrap();
+        }
+        assert_eq!(wheel.advance(2).collect::<Vec<_>>(), [2]);
+        assert_eq!(wheel.advance(4).collect::<Vec<_>>(), [6]);
+        assert_eq!(wheel.len(), 2);
+        assert_eq!(wheel.advance(100).collect::<Vec<_>>(), [10, 14]);
+        // Scheduling is relative to the current tick
+        wheel.schedule(5, 111).unwrap();
+        assert_eq!(wheel.advance(4).count(), 0);
+        assert_eq!(wheel.advance(1).collect::<Vec<_>>(), [111]);
+    }
+
+    #[test]
+    pub fn rejects_events_over_capacity() {
+        let mut wheel: TimerWheel<u8, 4, 2> = TimerWheel::new();
+        assert_eq!(wheel.schedule(1, 1), Ok(()));
+        assert_eq!(wheel.schedule(1, 2), Ok(()));
+        assert_eq!(wheel.schedule(1, 3), Err(3));
+        assert_eq!(wheel.advance(1).collect::<Vec<_>>(), [1, 2]);
+        assert_eq!(wheel.schedule(1, 3), Ok(()));
+    }
+
+    #[test]
+    pub fn advances_far_without_overflow() {
+        let mut wheel: TimerWheel<u8, 4, 4> = TimerWheel::new();
+        wheel.schedule(u64::MAX - 10, 1).unwrap();
+        wheel.schedule(3, 2).unwrap();
+        assert_eq!(wheel.advance(1).count(), 0);
+        // Jumps between the deadlines, rather than stepping through every tick
+        assert_eq!(wheel.advance(u64::MAX).collect::<Vec<_>>(), [2, 1]);
+        assert_eq!(wheel.now(), u64::MAX);
+        // Past the end of time, events are kept but never fire
+        wheel.schedule(5, 3).unwrap();
+        assert_eq!(wheel.advance(5).count(), 0);
+        assert_eq!(wheel.len(), 1);
+    }
+}